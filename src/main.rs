@@ -2,7 +2,6 @@ use std::fs;
 
 use clap::Parser;
 
-mod algorithms;
 mod util;
 mod y2015;
 mod y2022;
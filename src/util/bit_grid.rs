@@ -0,0 +1,404 @@
+use std::fmt;
+
+use super::grid::{Grid, Point};
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+// A grid of booleans packed 64 cells to a word. Each row starts on a fresh word and the
+// bits past `width` in the last word of a row are always kept clear.
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct BitGrid {
+    words: Vec<u64>,
+    words_per_row: usize,
+    pub width: usize,
+    pub height: usize,
+}
+
+#[allow(dead_code)]
+impl BitGrid {
+    pub fn new(width: usize, height: usize) -> Self {
+        let words_per_row = width.div_ceil(WORD_BITS);
+
+        Self {
+            words: vec![0; words_per_row * height],
+            words_per_row,
+            width,
+            height,
+        }
+    }
+
+    pub fn is_in_bounds(&self, point: &Point) -> bool {
+        point.x < self.width && point.y < self.height
+    }
+
+    pub fn get(&self, point: &Point) -> bool {
+        let (idx, bit) = self.word_bit(point);
+
+        self.words[idx] & bit != 0
+    }
+
+    pub fn set(&mut self, point: &Point, value: bool) {
+        let (idx, bit) = self.word_bit(point);
+
+        if value {
+            self.words[idx] |= bit;
+        } else {
+            self.words[idx] &= !bit;
+        }
+    }
+
+    pub fn toggle(&mut self, point: &Point) {
+        let (idx, bit) = self.word_bit(point);
+
+        self.words[idx] ^= bit;
+    }
+
+    // The rectangle operations include both corners, like the puzzle inputs that use them.
+    pub fn set_rect(&mut self, top_left: &Point, bottom_right: &Point, value: bool) {
+        if value {
+            self.apply_rect(top_left, bottom_right, |word, mask| *word |= mask);
+        } else {
+            self.apply_rect(top_left, bottom_right, |word, mask| *word &= !mask);
+        }
+    }
+
+    pub fn clear_rect(&mut self, top_left: &Point, bottom_right: &Point) {
+        self.set_rect(top_left, bottom_right, false);
+    }
+
+    pub fn toggle_rect(&mut self, top_left: &Point, bottom_right: &Point) {
+        self.apply_rect(top_left, bottom_right, |word, mask| *word ^= mask);
+    }
+
+    pub fn clear(&mut self) {
+        self.words.fill(0);
+    }
+
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn row_count_ones(&self, y: usize) -> usize {
+        self.row(y).iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn iter_ones(&self) -> impl Iterator<Item = Point> + '_ {
+        (0..self.height).flat_map(move |y| {
+            self.row(y).iter().enumerate().flat_map(move |(i, word)| {
+                let mut word = *word;
+
+                std::iter::from_fn(move || {
+                    if word == 0 {
+                        return None;
+                    }
+
+                    let bit = word.trailing_zeros() as usize;
+                    word &= word - 1;
+
+                    Some(Point::new(i * WORD_BITS + bit, y))
+                })
+            })
+        })
+    }
+
+    // Conway's Game of Life, B3/S23.
+    pub fn life_step(&self) -> Self {
+        self.step(&[3], &[2, 3])
+    }
+
+    // Advances a life-like automaton one generation. A dead cell with a live neighbour count
+    // in `birth` comes alive, and a live cell with a count in `survive` stays alive. Cells
+    // outside the grid count as dead.
+    //
+    // The eight neighbours of 64 cells at a time are added together as shifted words, keeping
+    // the per-cell counts as four bit planes.
+    pub fn step(&self, birth: &[u8], survive: &[u8]) -> Self {
+        let mut next = Self::new(self.width, self.height);
+        let empty = vec![0; self.words_per_row];
+
+        for y in 0..self.height {
+            let above = if y > 0 { self.row(y - 1) } else { &empty };
+            let below = if y + 1 < self.height {
+                self.row(y + 1)
+            } else {
+                &empty
+            };
+            let current = self.row(y);
+
+            for i in 0..self.words_per_row {
+                let mut counts = [0_u64; 4];
+
+                for row in [above, current, below] {
+                    Self::add_plane(&mut counts, Self::shifted_from_left(row, i));
+                    Self::add_plane(&mut counts, Self::shifted_from_right(row, i));
+                }
+                Self::add_plane(&mut counts, above[i]);
+                Self::add_plane(&mut counts, below[i]);
+
+                let alive = current[i];
+                let born = birth
+                    .iter()
+                    .fold(0, |acc, n| acc | Self::count_equals(&counts, *n));
+                let survived = survive
+                    .iter()
+                    .fold(0, |acc, n| acc | Self::count_equals(&counts, *n));
+
+                next.words[y * self.words_per_row + i] = (!alive & born) | (alive & survived);
+            }
+
+            next.mask_row(y);
+        }
+
+        next
+    }
+
+    fn row(&self, y: usize) -> &[u64] {
+        let start = y * self.words_per_row;
+
+        &self.words[start..start + self.words_per_row]
+    }
+
+    fn word_bit(&self, point: &Point) -> (usize, u64) {
+        assert!(
+            self.is_in_bounds(point),
+            "{point:?} is outside the {}x{} grid",
+            self.width,
+            self.height
+        );
+
+        let idx = point.y * self.words_per_row + point.x / WORD_BITS;
+
+        (idx, 1 << (point.x % WORD_BITS))
+    }
+
+    fn apply_rect<F>(&mut self, top_left: &Point, bottom_right: &Point, mut op: F)
+    where
+        F: FnMut(&mut u64, u64),
+    {
+        let x_start = top_left.x.min(bottom_right.x);
+        let x_end = top_left.x.max(bottom_right.x);
+        let y_start = top_left.y.min(bottom_right.y);
+        let y_end = top_left.y.max(bottom_right.y);
+
+        assert!(
+            x_end < self.width && y_end < self.height,
+            "rectangle {top_left:?} to {bottom_right:?} is outside the {}x{} grid",
+            self.width,
+            self.height
+        );
+
+        for y in y_start..=y_end {
+            for i in x_start / WORD_BITS..=x_end / WORD_BITS {
+                let lo = (i * WORD_BITS).max(x_start) - i * WORD_BITS;
+                let hi = ((i + 1) * WORD_BITS - 1).min(x_end) - i * WORD_BITS;
+
+                let mask = (u64::MAX >> (WORD_BITS - 1 - hi)) & (u64::MAX << lo);
+                op(&mut self.words[y * self.words_per_row + i], mask);
+            }
+        }
+    }
+
+    fn mask_row(&mut self, y: usize) {
+        let used = self.width % WORD_BITS;
+
+        if used != 0 {
+            let idx = (y + 1) * self.words_per_row - 1;
+            self.words[idx] &= (1 << used) - 1;
+        }
+    }
+
+    // Word `i` of the row where each cell holds its left neighbour's value.
+    fn shifted_from_left(row: &[u64], i: usize) -> u64 {
+        let carry = if i > 0 {
+            row[i - 1] >> (WORD_BITS - 1)
+        } else {
+            0
+        };
+
+        (row[i] << 1) | carry
+    }
+
+    // Word `i` of the row where each cell holds its right neighbour's value.
+    fn shifted_from_right(row: &[u64], i: usize) -> u64 {
+        let carry = if i + 1 < row.len() {
+            row[i + 1] << (WORD_BITS - 1)
+        } else {
+            0
+        };
+
+        (row[i] >> 1) | carry
+    }
+
+    fn add_plane(counts: &mut [u64; 4], plane: u64) {
+        let mut carry = plane;
+
+        for count in counts.iter_mut() {
+            let next_carry = *count & carry;
+            *count ^= carry;
+            carry = next_carry;
+        }
+    }
+
+    fn count_equals(counts: &[u64; 4], n: u8) -> u64 {
+        counts
+            .iter()
+            .enumerate()
+            .fold(u64::MAX, |acc, (bit, count)| {
+                if n & (1 << bit) != 0 {
+                    acc & count
+                } else {
+                    acc & !count
+                }
+            })
+    }
+}
+
+impl From<&Grid<bool>> for BitGrid {
+    fn from(grid: &Grid<bool>) -> Self {
+        let mut bit_grid = Self::new(grid.width, grid.height);
+
+        for (idx, _) in grid.iter().enumerate().filter(|(_, cell)| **cell) {
+            bit_grid.set(&grid.idx_point(idx), true);
+        }
+
+        bit_grid
+    }
+}
+
+impl From<&BitGrid> for Grid<bool> {
+    fn from(bit_grid: &BitGrid) -> Self {
+        let mut grid = Grid::new(bit_grid.width, bit_grid.height, false);
+
+        for point in bit_grid.iter_ones() {
+            grid[&point] = true;
+        }
+
+        grid
+    }
+}
+
+impl fmt::Display for BitGrid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let cell = if self.get(&Point::new(x, y)) {
+                    '#'
+                } else {
+                    '.'
+                };
+                write!(f, "{cell}")?;
+            }
+
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn parse(lines: &[&str]) -> BitGrid {
+        BitGrid::from(&Grid::parse(lines, |ch| ch == '#'))
+    }
+
+    #[test]
+    fn test_rect_operations() {
+        let mut grid = BitGrid::new(200, 3);
+
+        grid.set_rect(&Point::new(10, 0), &Point::new(150, 2), true);
+        assert_eq!(grid.count_ones(), 141 * 3);
+
+        grid.toggle_rect(&Point::new(60, 1), &Point::new(199, 1));
+        assert_eq!(grid.row_count_ones(1), 50 + 49);
+        assert!(grid.get(&Point::new(59, 1)));
+        assert!(!grid.get(&Point::new(60, 1)));
+        assert!(grid.get(&Point::new(151, 1)));
+
+        grid.clear_rect(&Point::new(0, 0), &Point::new(127, 2));
+        assert_eq!(grid.count_ones(), 23 + 49 + 23);
+
+        grid.toggle(&Point::new(199, 2));
+        grid.set(&Point::new(0, 0), true);
+        assert_eq!(
+            grid.iter_ones()
+                .filter(|p| p.x == 199 || p.x == 0)
+                .collect::<Vec<_>>(),
+            [Point::new(0, 0), Point::new(199, 1), Point::new(199, 2)]
+        );
+    }
+
+    #[test]
+    fn test_grid_conversion() {
+        #[rustfmt::skip]
+        let lines = [
+            ".#.#",
+            "#..#",
+            "....",
+        ];
+
+        let grid = Grid::parse(&lines, |ch| ch == '#');
+        let bit_grid = BitGrid::from(&grid);
+
+        assert_eq!(bit_grid.count_ones(), 4);
+        assert_eq!(Grid::from(&bit_grid), grid);
+        assert_eq!(bit_grid.to_string(), lines.join("\n") + "\n");
+    }
+
+    #[test]
+    fn test_life_step() {
+        // 2015 day 18 example
+        #[rustfmt::skip]
+        let mut grid = parse(&[
+            ".#.#.#",
+            "...##.",
+            "#....#",
+            "..#...",
+            "#.#..#",
+            "####..",
+        ]);
+
+        for _ in 0..4 {
+            grid = grid.life_step();
+        }
+
+        #[rustfmt::skip]
+        let expected = parse(&[
+            "......",
+            "......",
+            "..##..",
+            "..##..",
+            "......",
+            "......",
+        ]);
+
+        assert_eq!(grid, expected);
+    }
+
+    #[test]
+    fn test_life_step_across_words() {
+        // a blinker straddling the boundary between the first two words of each row
+        let mut grid = BitGrid::new(130, 3);
+        grid.set_rect(&Point::new(63, 1), &Point::new(65, 1), true);
+
+        let next = grid.life_step();
+
+        assert_eq!(
+            next.iter_ones().collect::<Vec<_>>(),
+            [Point::new(64, 0), Point::new(64, 1), Point::new(64, 2)]
+        );
+        assert_eq!(next.life_step(), grid);
+
+        // cells past the width never come alive
+        let mut grid = BitGrid::new(65, 3);
+        grid.set_rect(&Point::new(64, 0), &Point::new(64, 2), true);
+
+        assert_eq!(
+            grid.life_step().iter_ones().collect::<Vec<_>>(),
+            [Point::new(63, 1), Point::new(64, 1)]
+        );
+    }
+}
@@ -0,0 +1,365 @@
+// Lazy enumeration of arrangements. Each enumerator reuses one buffer, `next_slice` hands out
+// a view of it without allocating, and the Iterator impls copy it into a Vec for convenience.
+
+// Every ordering of `items`, by Heap's algorithm so each step is a single swap.
+#[derive(Debug, Clone)]
+pub struct Permutations<T> {
+    items: Vec<T>,
+    counters: Vec<usize>,
+    position: usize,
+    started: bool,
+}
+
+// Every way of choosing `k` of the items, in lexicographic order of their indexes.
+#[derive(Debug, Clone)]
+pub struct Combinations<'a, T> {
+    items: &'a [T],
+    indexes: Vec<usize>,
+    current: Vec<T>,
+    started: bool,
+    done: bool,
+}
+
+// Every sequence of `length` symbols from `alphabet`, counting like an odometer with the last
+// symbol changing fastest.
+#[derive(Debug, Clone)]
+pub struct CartesianPower<'a, T> {
+    alphabet: &'a [T],
+    indexes: Vec<usize>,
+    current: Vec<T>,
+    started: bool,
+    done: bool,
+}
+
+#[allow(dead_code)]
+pub fn permutations<T: Clone>(items: &[T]) -> Permutations<T> {
+    Permutations {
+        items: items.to_vec(),
+        counters: vec![0; items.len()],
+        position: 0,
+        started: false,
+    }
+}
+
+#[allow(dead_code)]
+pub fn combinations<T: Clone>(items: &[T], k: usize) -> Combinations<'_, T> {
+    Combinations {
+        items,
+        indexes: (0..k).collect(),
+        current: items.iter().take(k).cloned().collect(),
+        started: false,
+        done: k > items.len(),
+    }
+}
+
+#[allow(dead_code)]
+pub fn cartesian_power<T: Clone>(alphabet: &[T], length: usize) -> CartesianPower<'_, T> {
+    CartesianPower {
+        alphabet,
+        indexes: vec![0; length],
+        current: alphabet
+            .first()
+            .map_or(vec![], |first| vec![first.clone(); length]),
+        started: false,
+        done: alphabet.is_empty() && length > 0,
+    }
+}
+
+// Every subset of `items`, in order of the bit masks that select them.
+#[allow(dead_code)]
+pub fn power_set<T: Clone>(items: &[T]) -> impl Iterator<Item = Vec<T>> + '_ {
+    assert!(items.len() < 64, "power_set: too many items");
+
+    (0_u64..1 << items.len()).map(|mask| {
+        items
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| mask & 1 << i != 0)
+            .map(|(_, item)| item.clone())
+            .collect()
+    })
+}
+
+#[allow(dead_code)]
+impl<T> Permutations<T> {
+    pub fn next_slice(&mut self) -> Option<&[T]> {
+        if !self.started {
+            self.started = true;
+            return Some(&self.items);
+        }
+
+        while self.position < self.items.len() {
+            if self.counters[self.position] < self.position {
+                let other = if self.position.is_multiple_of(2) {
+                    0
+                } else {
+                    self.counters[self.position]
+                };
+                self.items.swap(other, self.position);
+                self.counters[self.position] += 1;
+                self.position = 0;
+
+                return Some(&self.items);
+            }
+
+            self.counters[self.position] = 0;
+            self.position += 1;
+        }
+
+        None
+    }
+}
+
+#[allow(dead_code)]
+impl<T: Clone> Combinations<'_, T> {
+    pub fn next_slice(&mut self) -> Option<&[T]> {
+        if self.done {
+            return None;
+        }
+
+        if !self.started {
+            self.started = true;
+            return Some(&self.current);
+        }
+
+        let (n, k) = (self.items.len(), self.indexes.len());
+
+        // the rightmost index that can still move right
+        let Some(i) = (0..k).rev().find(|i| self.indexes[*i] < n - k + i) else {
+            self.done = true;
+            return None;
+        };
+
+        self.indexes[i] += 1;
+        for j in i + 1..k {
+            self.indexes[j] = self.indexes[j - 1] + 1;
+        }
+        for j in i..k {
+            self.current[j] = self.items[self.indexes[j]].clone();
+        }
+
+        Some(&self.current)
+    }
+}
+
+#[allow(dead_code)]
+impl<T: Clone> CartesianPower<'_, T> {
+    pub fn next_slice(&mut self) -> Option<&[T]> {
+        if self.done {
+            return None;
+        }
+
+        if !self.started {
+            self.started = true;
+            return Some(&self.current);
+        }
+
+        let Some(i) = (0..self.indexes.len())
+            .rev()
+            .find(|i| self.indexes[*i] + 1 < self.alphabet.len())
+        else {
+            self.done = true;
+            return None;
+        };
+
+        self.indexes[i] += 1;
+        self.current[i] = self.alphabet[self.indexes[i]].clone();
+        for j in i + 1..self.indexes.len() {
+            self.indexes[j] = 0;
+            self.current[j] = self.alphabet[0].clone();
+        }
+
+        Some(&self.current)
+    }
+
+    // Skips the rest of the sequences that start with the first `prefix` symbols of the last
+    // one returned, for pruning a search once a prefix is known to be hopeless.
+    pub fn skip_prefix(&mut self, prefix: usize) {
+        if prefix == 0 {
+            self.done = true;
+            return;
+        }
+
+        for i in prefix..self.indexes.len() {
+            self.indexes[i] = self.alphabet.len() - 1;
+        }
+    }
+}
+
+impl<T: Clone> Iterator for Permutations<T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Vec<T>> {
+        self.next_slice().map(<[T]>::to_vec)
+    }
+}
+
+impl<T: Clone> Iterator for Combinations<'_, T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Vec<T>> {
+        self.next_slice().map(<[T]>::to_vec)
+    }
+}
+
+impl<T: Clone> Iterator for CartesianPower<'_, T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Vec<T>> {
+        self.next_slice().map(<[T]>::to_vec)
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Route {
+    // Visit every node once, starting and ending anywhere.
+    Path,
+    // Visit every node once and return to the start.
+    Cycle,
+}
+
+// The cheapest route through every node by Held-Karp, O(2^n * n^2) rather than n!. The cost
+// of going from `a` to `b` is `weights[a][b]`, negate the weights to find the dearest route.
+#[allow(dead_code)]
+pub fn held_karp(weights: &[Vec<i64>], route: Route) -> Option<i64> {
+    let n = weights.len();
+    if n == 0 {
+        return None;
+    }
+    assert!(n < 32, "held_karp: too many nodes");
+
+    // cheapest[visited][last], a cycle can be fixed to start at node 0
+    let full = (1 << n) - 1;
+    let mut cheapest = vec![vec![None; n]; 1 << n];
+    match route {
+        Route::Path => (0..n).for_each(|node| cheapest[1 << node][node] = Some(0)),
+        Route::Cycle => cheapest[1][0] = Some(0),
+    }
+
+    for visited in 1..=full {
+        for last in 0..n {
+            let Some(cost) = cheapest[visited][last] else {
+                continue;
+            };
+
+            for next in (0..n).filter(|next| visited & 1 << next == 0) {
+                let candidate = cost + weights[last][next];
+                let entry = &mut cheapest[visited | 1 << next][next];
+
+                if entry.is_none_or(|best| candidate < best) {
+                    *entry = Some(candidate);
+                }
+            }
+        }
+    }
+
+    (0..n)
+        .filter_map(|last| {
+            cheapest[full][last].map(|cost| match route {
+                Route::Path => cost,
+                Route::Cycle => cost + weights[last][0],
+            })
+        })
+        .min()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_permutations() {
+        let all = permutations(&[1, 2, 3]).collect::<Vec<_>>();
+        assert_eq!(
+            all,
+            [
+                [1, 2, 3],
+                [2, 1, 3],
+                [3, 1, 2],
+                [1, 3, 2],
+                [2, 3, 1],
+                [3, 2, 1]
+            ]
+        );
+
+        let distinct = permutations(&[0, 1, 2, 3, 4, 5]).collect::<HashSet<_>>();
+        assert_eq!(distinct.len(), 720);
+
+        assert_eq!(permutations::<u8>(&[]).count(), 1);
+    }
+
+    #[test]
+    fn test_combinations() {
+        let all = combinations(&['a', 'b', 'c', 'd'], 2)
+            .map(|pair| pair.iter().collect::<String>())
+            .collect::<Vec<_>>();
+        assert_eq!(all, ["ab", "ac", "ad", "bc", "bd", "cd"]);
+
+        assert_eq!(combinations(&[0; 10], 3).count(), 120);
+        assert_eq!(combinations(&[1, 2], 0).count(), 1);
+        assert_eq!(combinations(&[1, 2], 3).count(), 0);
+    }
+
+    #[test]
+    fn test_power_set() {
+        let all = power_set(&[1, 2, 3]).collect::<Vec<_>>();
+        assert_eq!(
+            all,
+            [
+                vec![],
+                vec![1],
+                vec![2],
+                vec![1, 2],
+                vec![3],
+                vec![1, 3],
+                vec![2, 3],
+                vec![1, 2, 3]
+            ]
+        );
+    }
+
+    #[test]
+    fn test_cartesian_power() {
+        let all = cartesian_power(&['+', '*'], 2)
+            .map(|ops| ops.iter().collect::<String>())
+            .collect::<Vec<_>>();
+        assert_eq!(all, ["++", "+*", "*+", "**"]);
+
+        assert_eq!(cartesian_power(&[0, 1, 2], 4).count(), 81);
+        assert_eq!(cartesian_power(&[0, 1], 0).count(), 1);
+        assert_eq!(cartesian_power::<u8>(&[], 2).count(), 0);
+
+        // skip everything starting with 0, 1
+        let mut sequences = cartesian_power(&[0, 1, 2], 3);
+        let mut seen = vec![];
+        while let Some(sequence) = sequences.next_slice() {
+            seen.push(sequence.to_vec());
+            if sequence[..2] == [0, 1] {
+                sequences.skip_prefix(2);
+            }
+        }
+        assert_eq!(seen.len(), 27 - 2);
+        assert!(!seen.contains(&vec![0, 1, 1]));
+        assert!(seen.contains(&vec![0, 2, 0]));
+    }
+
+    #[test]
+    fn test_held_karp() {
+        // London, Dublin, Belfast from 2015 day 9
+        let weights = vec![vec![0, 464, 518], vec![464, 0, 141], vec![518, 141, 0]];
+        assert_eq!(held_karp(&weights, Route::Path), Some(605));
+        assert_eq!(held_karp(&weights, Route::Cycle), Some(1123));
+
+        let negated = weights
+            .iter()
+            .map(|row| row.iter().map(|w| -w).collect())
+            .collect::<Vec<_>>();
+        assert_eq!(held_karp(&negated, Route::Path), Some(-982));
+
+        assert_eq!(held_karp(&[], Route::Path), None);
+        assert_eq!(held_karp(&[vec![0]], Route::Cycle), Some(0));
+    }
+}
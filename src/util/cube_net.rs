@@ -0,0 +1,300 @@
+use anyhow::{Result, anyhow};
+use std::collections::{HashMap, VecDeque};
+
+use super::point::{GridDirection, Point, Point3};
+
+// Where a face of the net ends up once folded. `right` and `down` are the directions the
+// face's own x and y run in, `normal` points out of the cube.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+struct Face {
+    corner: Point,
+    normal: Point3,
+    right: Point3,
+    down: Point3,
+}
+
+impl Face {
+    fn axis(&self, direction: GridDirection) -> Point3 {
+        match direction {
+            GridDirection::Right => self.right,
+            GridDirection::Left => -self.right,
+            GridDirection::Down => self.down,
+            GridDirection::Up => -self.down,
+            _ => panic!("A cube net can only be walked in cardinal directions, not {direction:?}"),
+        }
+    }
+
+    // The face over the edge in `direction`, bent down around the cube.
+    fn fold(&self, direction: GridDirection, corner: Point) -> Self {
+        let normal = self.axis(direction);
+        let forward = -self.normal;
+
+        let (right, down) = match direction {
+            GridDirection::Right => (forward, self.down),
+            GridDirection::Left => (-forward, self.down),
+            GridDirection::Down => (self.right, forward),
+            GridDirection::Up => (self.right, -forward),
+            _ => unreachable!(),
+        };
+
+        Self {
+            corner,
+            normal,
+            right,
+            down,
+        }
+    }
+}
+
+fn dot(a: Point3, b: Point3) -> i64 {
+    a.x * b.x + a.y * b.y + a.z * b.z
+}
+
+// A flat map of six square faces, like the ones in 2022 day 22, folded up into a cube so that
+// walking off the edge of one face carries on across the face it is glued to. Spaces in the
+// map are outside the net, anything else is part of a face.
+#[derive(Debug, Clone)]
+pub struct CubeNet {
+    size: usize,
+    faces: Vec<Face>,
+    // faces by their position in the net, counted in whole faces
+    blocks: HashMap<Point, usize>,
+}
+
+#[allow(dead_code)]
+impl CubeNet {
+    pub fn new(map: &[&str]) -> Result<Self> {
+        let on_net = |x: usize, y: usize| {
+            map.get(y)
+                .and_then(|row| row.as_bytes().get(x))
+                .is_some_and(|tile| *tile != b' ')
+        };
+
+        let cells = map
+            .iter()
+            .map(|row| row.bytes().filter(|tile| *tile != b' ').count())
+            .sum::<usize>();
+        let size = (cells / 6).isqrt();
+        if size == 0 || size * size * 6 != cells {
+            return Err(anyhow!(
+                "CubeNet::new: {cells} cells can't make six square faces"
+            ));
+        }
+
+        let width = map.iter().map(|row| row.len()).max().unwrap_or_default();
+        let corners = (0..map.len().div_ceil(size))
+            .flat_map(|y| (0..width.div_ceil(size)).map(move |x| Point::new(x, y)))
+            .filter(|block| on_net(block.x * size, block.y * size))
+            .collect::<Vec<_>>();
+        if corners.len() != 6 {
+            return Err(anyhow!(
+                "CubeNet::new: found {} faces of size {size}, not 6",
+                corners.len()
+            ));
+        }
+
+        let first = Face {
+            corner: corners[0] * size,
+            normal: Point3::new(0, 0, -1),
+            right: Point3::new(1, 0, 0),
+            down: Point3::new(0, 1, 0),
+        };
+        let mut faces = vec![first];
+        let mut blocks = HashMap::from([(corners[0], 0)]);
+        let mut queue = VecDeque::from([corners[0]]);
+
+        while let Some(block) = queue.pop_front() {
+            let face = faces[blocks[&block]];
+
+            for direction in GridDirection::cardinal() {
+                let Some(next) = block.get_adjacent(direction) else {
+                    continue;
+                };
+                if !corners.contains(&next) || blocks.contains_key(&next) {
+                    continue;
+                }
+
+                blocks.insert(next, faces.len());
+                faces.push(face.fold(direction, next * size));
+                queue.push_back(next);
+            }
+        }
+
+        if faces.len() != 6 {
+            return Err(anyhow!("CubeNet::new: the faces are not all joined up"));
+        }
+        for (index, face) in faces.iter().enumerate() {
+            if faces[..index]
+                .iter()
+                .any(|other| other.normal == face.normal)
+            {
+                return Err(anyhow!("CubeNet::new: the faces overlap when folded"));
+            }
+        }
+
+        Ok(Self {
+            size,
+            faces,
+            blocks,
+        })
+    }
+
+    // The length of each edge of the cube.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    // Which face a point of the map is on, faces are numbered in the order they are folded.
+    pub fn face_of(&self, point: Point) -> Option<usize> {
+        self.blocks
+            .get(&Point::new(point.x / self.size, point.y / self.size))
+            .copied()
+    }
+
+    // The top left corner of a face in the map.
+    pub fn corner(&self, face: usize) -> Point {
+        self.faces[face].corner
+    }
+
+    // The face glued to the `direction` edge of `face`, and the way you are facing once you
+    // have walked across onto it.
+    pub fn neighbour(&self, face: usize, direction: GridDirection) -> (usize, GridDirection) {
+        let from = &self.faces[face];
+        let normal = from.axis(direction);
+
+        let to = self
+            .faces
+            .iter()
+            .position(|face| face.normal == normal)
+            .unwrap();
+        let facing = GridDirection::cardinal()
+            .into_iter()
+            .find(|facing| self.faces[to].axis(*facing) == -from.normal)
+            .unwrap();
+
+        (to, facing)
+    }
+
+    // One step from `point`, going over onto the next face if it is on an edge. None if the
+    // point isn't on the net.
+    pub fn step(&self, point: Point, facing: GridDirection) -> Option<(Point, GridDirection)> {
+        let face = self.face_of(point)?;
+
+        if let Some(next) = point.get_adjacent(facing)
+            && self.face_of(next) == Some(face)
+        {
+            return Some((next, facing));
+        }
+
+        // Centres of the cells on the surface of a cube 2 * size across, centred on the origin.
+        let from = &self.faces[face];
+        let size = self.size as i64;
+        let offset = point - from.corner;
+        let centre = from.normal * size
+            + from.right * (2 * offset.x as i64 + 1 - size)
+            + from.down * (2 * offset.y as i64 + 1 - size);
+        let centre = centre + from.axis(facing) - from.normal;
+
+        let (to, facing) = self.neighbour(face, facing);
+        let to = &self.faces[to];
+        let local = |axis| ((dot(centre, axis) + size - 1) / 2) as usize;
+
+        Some((
+            to.corner + Point::new(local(to.right), local(to.down)),
+            facing,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[rustfmt::skip]
+    const EXAMPLE: [&str; 12] = [
+        "        ...#",
+        "        .#..",
+        "        #...",
+        "        ....",
+        "...#.......#",
+        "........#...",
+        "..#....#....",
+        "..........#.",
+        "        ...#....",
+        "        .....#..",
+        "        .#......",
+        "        ......#.",
+    ];
+
+    #[test]
+    fn test_cube_net_fold() {
+        let net = CubeNet::new(&EXAMPLE).unwrap();
+
+        assert_eq!(net.size(), 4);
+        assert_eq!(net.face_of(Point::new(8, 0)), Some(0));
+        assert_eq!(net.face_of(Point::new(0, 0)), None);
+
+        // each face touches four different faces, none of them itself
+        for face in 0..6 {
+            let mut neighbours = GridDirection::cardinal()
+                .iter()
+                .map(|direction| net.neighbour(face, *direction).0)
+                .collect::<Vec<_>>();
+            neighbours.sort();
+            neighbours.dedup();
+
+            assert_eq!(neighbours.len(), 4);
+            assert!(!neighbours.contains(&face));
+        }
+    }
+
+    #[test]
+    fn test_cube_net_step() {
+        let net = CubeNet::new(&EXAMPLE).unwrap();
+
+        let cases = [
+            // along a face
+            (
+                (Point::new(9, 1), GridDirection::Right),
+                (Point::new(10, 1), GridDirection::Right),
+            ),
+            // the examples from the puzzle
+            (
+                (Point::new(11, 5), GridDirection::Right),
+                (Point::new(14, 8), GridDirection::Down),
+            ),
+            (
+                (Point::new(10, 11), GridDirection::Down),
+                (Point::new(1, 7), GridDirection::Up),
+            ),
+            (
+                (Point::new(6, 4), GridDirection::Up),
+                (Point::new(8, 2), GridDirection::Right),
+            ),
+        ];
+
+        for ((point, facing), expected) in cases {
+            assert_eq!(
+                net.step(point, facing),
+                Some(expected),
+                "{point:?} {facing:?}"
+            );
+
+            // and straight back again
+            let (back, turned) = net.step(expected.0, expected.1.opposite()).unwrap();
+            assert_eq!((back, turned.opposite()), (point, facing));
+        }
+
+        assert_eq!(net.step(Point::new(0, 0), GridDirection::Up), None);
+    }
+
+    #[test]
+    fn test_cube_net_errors() {
+        assert!(CubeNet::new(&["...."]).is_err());
+        assert!(CubeNet::new(&["......"]).is_err());
+        assert!(CubeNet::new(&[".", ".", ".", ".", ".", "."]).is_err());
+        assert!(CubeNet::new(&["...", "", ". .", " ."]).is_err());
+        assert!(CubeNet::new(&[" .", "...", " .", " ."]).is_ok());
+    }
+}
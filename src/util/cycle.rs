@@ -0,0 +1,169 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct Cycle {
+    pub start: usize,
+    pub len: usize,
+}
+
+#[allow(dead_code)]
+impl Cycle {
+    // Maps step `n` onto the first step that produces the same state.
+    pub fn equivalent_step(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.len
+        }
+    }
+}
+
+#[allow(dead_code)]
+pub fn find_cycle_hash<S, F>(initial: S, mut step: F) -> Cycle
+where
+    S: Clone + Eq + Hash,
+    F: FnMut(&S) -> S,
+{
+    let mut seen = HashMap::new();
+    let mut state = initial;
+    let mut i = 0;
+
+    loop {
+        if let Some(&start) = seen.get(&state) {
+            return Cycle {
+                start,
+                len: i - start,
+            };
+        }
+
+        let next = step(&state);
+        seen.insert(state, i);
+        state = next;
+        i += 1;
+    }
+}
+
+#[allow(dead_code)]
+pub fn find_cycle_brent<S, F>(initial: S, mut step: F) -> Cycle
+where
+    S: Clone + Eq,
+    F: FnMut(&S) -> S,
+{
+    let mut power = 1;
+    let mut len = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+
+    while tortoise != hare {
+        if power == len {
+            tortoise = hare.clone();
+            power *= 2;
+            len = 0;
+        }
+
+        hare = step(&hare);
+        len += 1;
+    }
+
+    let mut tortoise = initial.clone();
+    let mut hare = initial;
+    for _ in 0..len {
+        hare = step(&hare);
+    }
+
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    Cycle { start, len }
+}
+
+#[allow(dead_code)]
+pub fn state_at<S, F>(initial: S, mut step: F, n: usize) -> S
+where
+    S: Clone + Eq + Hash,
+    F: FnMut(&S) -> S,
+{
+    let mut seen = HashMap::new();
+    let mut history = vec![];
+    let mut state = initial;
+
+    for i in 0..n {
+        if let Some(&start) = seen.get(&state) {
+            let cycle = Cycle {
+                start,
+                len: i - start,
+            };
+
+            return history.swap_remove(cycle.equivalent_step(n));
+        }
+
+        let next = step(&state);
+        seen.insert(state.clone(), i);
+        history.push(state);
+        state = next;
+    }
+
+    state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 0 -> 1 -> 2 -> 3 -> 4 -> 5 -> 2 -> ...
+    fn rho_step(n: &u32) -> u32 {
+        if *n == 5 { 2 } else { n + 1 }
+    }
+
+    #[test]
+    fn test_find_cycle() {
+        let expected = Cycle { start: 2, len: 4 };
+
+        assert_eq!(find_cycle_hash(0, rho_step), expected);
+        assert_eq!(find_cycle_brent(0, rho_step), expected);
+
+        let expected = Cycle { start: 0, len: 7 };
+        let step = |n: &u64| (n + 3) % 7;
+
+        assert_eq!(find_cycle_hash(0, step), expected);
+        assert_eq!(find_cycle_brent(0, step), expected);
+    }
+
+    #[test]
+    fn test_find_cycle_brent_matches_hash() {
+        let step = |n: &u64| (n * n + 1) % 255;
+
+        for initial in 0..255 {
+            assert_eq!(
+                find_cycle_brent(initial, step),
+                find_cycle_hash(initial, step),
+                "Cycle mismatch with initial state {initial}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_state_at() {
+        for (n, expected) in [
+            (0, 0),
+            (1, 1),
+            (2, 2),
+            (5, 5),
+            (6, 2),
+            (9, 5),
+            (10, 2),
+            (1_000_000_000_000_000_000, 4),
+        ] {
+            let actual = state_at(0, rho_step, n);
+            assert_eq!(
+                actual, expected,
+                "Got {actual} when expecting {expected} from state_at with n: {n}"
+            );
+        }
+    }
+}
@@ -0,0 +1,146 @@
+use std::cell::Cell;
+use std::collections::HashMap;
+
+// Union-find over the elements 0..len, with path compression and union by size.
+#[derive(Debug, Clone)]
+pub struct DisjointSet {
+    parents: Vec<Cell<usize>>,
+    sizes: Vec<usize>,
+    component_count: usize,
+}
+
+#[allow(dead_code)]
+impl DisjointSet {
+    pub fn new(len: usize) -> Self {
+        Self {
+            parents: (0..len).map(Cell::new).collect(),
+            sizes: vec![1; len],
+            component_count: len,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.parents.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parents.is_empty()
+    }
+
+    // The representative of the component holding `element`. The path is compressed as we
+    // go, which only touches the cells so lookups can be made through a shared reference.
+    pub fn find(&self, element: usize) -> usize {
+        let mut root = element;
+        while self.parents[root].get() != root {
+            root = self.parents[root].get();
+        }
+
+        let mut current = element;
+        while current != root {
+            current = self.parents[current].replace(root);
+        }
+
+        root
+    }
+
+    // Merges the components of `a` and `b`, returns false if they were already connected.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+
+        if self.sizes[a] < self.sizes[b] {
+            std::mem::swap(&mut a, &mut b);
+        }
+
+        self.parents[b].set(a);
+        self.sizes[a] += self.sizes[b];
+        self.component_count -= 1;
+
+        true
+    }
+
+    pub fn is_connected(&self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    pub fn component_count(&self) -> usize {
+        self.component_count
+    }
+
+    pub fn component_size(&self, element: usize) -> usize {
+        self.sizes[self.find(element)]
+    }
+
+    // The size of every component, largest first.
+    pub fn component_sizes(&self) -> Vec<usize> {
+        let mut sizes = (0..self.len())
+            .filter(|element| self.find(*element) == *element)
+            .map(|root| self.sizes[root])
+            .collect::<Vec<_>>();
+
+        sizes.sort_by(|a, b| b.cmp(a));
+
+        sizes
+    }
+
+    // The members of every component, each sorted, ordered by their smallest member.
+    pub fn components(&self) -> Vec<Vec<usize>> {
+        let mut components: HashMap<usize, Vec<usize>> = HashMap::new();
+
+        for element in 0..self.len() {
+            components
+                .entry(self.find(element))
+                .or_default()
+                .push(element);
+        }
+
+        let mut components = components.into_values().collect::<Vec<_>>();
+        components.sort_by_key(|members| members[0]);
+
+        components
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_union_find() {
+        let mut set = DisjointSet::new(8);
+        assert_eq!(set.component_count(), 8);
+
+        assert!(set.union(0, 1));
+        assert!(set.union(2, 3));
+        assert!(set.union(1, 3));
+        assert!(!set.union(0, 2));
+        assert!(set.union(5, 6));
+
+        assert!(set.is_connected(0, 3));
+        assert!(!set.is_connected(0, 4));
+        assert_eq!(set.component_count(), 4);
+        assert_eq!(set.component_size(2), 4);
+        assert_eq!(set.component_size(4), 1);
+        assert_eq!(set.component_sizes(), [4, 2, 1, 1]);
+        assert_eq!(
+            set.components(),
+            [vec![0, 1, 2, 3], vec![4], vec![5, 6], vec![7]]
+        );
+    }
+
+    #[test]
+    fn test_path_compression() {
+        let mut set = DisjointSet::new(1000);
+
+        for element in 1..1000 {
+            set.union(element - 1, element);
+        }
+
+        let root = set.find(999);
+        assert!((0..1000).all(|element| set.parents[element].get() == root));
+        assert_eq!(set.component_sizes(), [1000]);
+    }
+}
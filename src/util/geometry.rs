@@ -0,0 +1,292 @@
+use std::cmp::{max, min};
+
+use super::number_theory::greatest_common_divisor;
+use super::point::Point;
+
+// Polygons are given as their vertices in order, the closing edge from the last vertex back
+// to the first is implied. Vertices are on the integer lattice so every result is exact.
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum PointLocation {
+    Inside,
+    Boundary,
+    Outside,
+}
+
+fn edges(vertices: &[Point<i64>]) -> impl Iterator<Item = (Point<i64>, Point<i64>)> + '_ {
+    vertices
+        .iter()
+        .zip(vertices.iter().cycle().skip(1))
+        .map(|(a, b)| (*a, *b))
+}
+
+// Twice the signed area from the shoelace formula. Positive when the vertices go
+// counter-clockwise with y growing upwards, which is clockwise on screen where y grows down.
+pub fn signed_double_area(vertices: &[Point<i64>]) -> i64 {
+    edges(vertices).map(|(a, b)| a.x * b.y - b.x * a.y).sum()
+}
+
+// Twice the area, doubled so polygons with a half unit area stay exact.
+pub fn double_area(vertices: &[Point<i64>]) -> i64 {
+    signed_double_area(vertices).abs()
+}
+
+// The number of lattice points on the edges.
+pub fn boundary_points(vertices: &[Point<i64>]) -> i64 {
+    edges(vertices)
+        .map(|(a, b)| greatest_common_divisor((a.x - b.x).abs(), (a.y - b.y).abs()))
+        .sum()
+}
+
+// The number of lattice points strictly inside, from Pick's theorem A = I + B / 2 - 1.
+pub fn interior_points(vertices: &[Point<i64>]) -> i64 {
+    (double_area(vertices) - boundary_points(vertices) + 2) / 2
+}
+
+// Interior and boundary points together, the cells covered when each vertex is a cell.
+pub fn lattice_points(vertices: &[Point<i64>]) -> i64 {
+    interior_points(vertices) + boundary_points(vertices)
+}
+
+fn is_on_segment(point: &Point<i64>, a: &Point<i64>, b: &Point<i64>) -> bool {
+    let cross = (b.x - a.x) as i128 * (point.y - a.y) as i128
+        - (point.x - a.x) as i128 * (b.y - a.y) as i128;
+
+    cross == 0
+        && (min(a.x, b.x)..=max(a.x, b.x)).contains(&point.x)
+        && (min(a.y, b.y)..=max(a.y, b.y)).contains(&point.y)
+}
+
+// Casts a ray towards positive x and counts the edges it crosses.
+pub fn locate_point(point: &Point<i64>, vertices: &[Point<i64>]) -> PointLocation {
+    let mut inside = false;
+
+    for (a, b) in edges(vertices) {
+        if is_on_segment(point, &a, &b) {
+            return PointLocation::Boundary;
+        }
+
+        if (a.y > point.y) != (b.y > point.y) {
+            // which side of the edge the point is on, flipped for downward edges so that
+            // a positive value always means the crossing is to the right of the point
+            let cross = (b.x - a.x) as i128 * (point.y - a.y) as i128
+                - (point.x - a.x) as i128 * (b.y - a.y) as i128;
+
+            if (cross > 0) == (b.y > a.y) {
+                inside = !inside;
+            }
+        }
+    }
+
+    if inside {
+        PointLocation::Inside
+    } else {
+        PointLocation::Outside
+    }
+}
+
+pub fn contains_point(point: &Point<i64>, vertices: &[Point<i64>]) -> bool {
+    locate_point(point, vertices) != PointLocation::Outside
+}
+
+// Whether the axis aligned rectangle with opposite corners `a` and `b` lies inside the
+// polygon, touching the boundary is allowed. Every edge must be horizontal or vertical.
+pub fn rectangle_in_rectilinear_polygon(
+    a: &Point<i64>,
+    b: &Point<i64>,
+    vertices: &[Point<i64>],
+) -> bool {
+    let (min_x, max_x) = (min(a.x, b.x), max(a.x, b.x));
+    let (min_y, max_y) = (min(a.y, b.y), max(a.y, b.y));
+
+    let corners = [
+        Point::new(min_x, min_y),
+        Point::new(max_x, min_y),
+        Point::new(min_x, max_y),
+        Point::new(max_x, max_y),
+    ];
+    if !corners
+        .iter()
+        .all(|corner| contains_point(corner, vertices))
+    {
+        return false;
+    }
+
+    let doubled = vertices
+        .iter()
+        .map(|vertex| *vertex * 2)
+        .collect::<Vec<_>>();
+
+    // a flat rectangle is a segment, which has no inside for an edge to cross. The boundary
+    // can only leave it at a vertex's coordinate, so check the midpoint of every piece
+    // between those, doubling everything to keep it on the lattice.
+    if min_x == max_x || min_y == max_y {
+        let horizontal = min_y == max_y;
+        let (start, end) = if horizontal {
+            (min_x, max_x)
+        } else {
+            (min_y, max_y)
+        };
+        let at = |along: i64, across: i64| match horizontal {
+            true => Point::new(along, across),
+            false => Point::new(across, along),
+        };
+
+        let mut stops = vertices
+            .iter()
+            .map(|vertex| if horizontal { vertex.x } else { vertex.y })
+            .filter(|stop| start < *stop && *stop < end)
+            .chain([start, end])
+            .collect::<Vec<_>>();
+        stops.sort();
+        stops.dedup();
+
+        let across = if horizontal { min_y } else { min_x };
+        return stops.windows(2).all(|pair| {
+            contains_point(&at(pair[0], across), vertices)
+                && contains_point(&at(pair[0] + pair[1], across * 2), &doubled)
+        });
+    }
+
+    // no edge may pass through the inside of the rectangle
+    let crosses = edges(vertices).any(|(p, q)| {
+        let (edge_min_x, edge_max_x) = (min(p.x, q.x), max(p.x, q.x));
+        let (edge_min_y, edge_max_y) = (min(p.y, q.y), max(p.y, q.y));
+
+        if p.x == q.x {
+            min_x < p.x && p.x < max_x && edge_min_y < max_y && min_y < edge_max_y
+        } else {
+            min_y < p.y && p.y < max_y && edge_min_x < max_x && min_x < edge_max_x
+        }
+    });
+    if crosses {
+        return false;
+    }
+
+    // with the corners on the boundary the rectangle could still be filling a notch that is
+    // outside, so check the centre too
+    contains_point(&Point::new(min_x + max_x, min_y + max_y), &doubled)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn polygon(vertices: &[(i64, i64)]) -> Vec<Point<i64>> {
+        vertices.iter().map(|(x, y)| Point::new(*x, *y)).collect()
+    }
+
+    #[test]
+    fn test_area() {
+        let square = polygon(&[(0, 0), (4, 0), (4, 4), (0, 4)]);
+        assert_eq!(signed_double_area(&square), 32);
+        assert_eq!(boundary_points(&square), 16);
+        assert_eq!(interior_points(&square), 9);
+        assert_eq!(lattice_points(&square), 25);
+
+        let reversed = square.iter().rev().copied().collect::<Vec<_>>();
+        assert_eq!(signed_double_area(&reversed), -32);
+        assert_eq!(interior_points(&reversed), 9);
+
+        // half unit area stays exact
+        let triangle = polygon(&[(0, 0), (3, 0), (0, 3)]);
+        assert_eq!(double_area(&triangle), 9);
+        assert_eq!(boundary_points(&triangle), 9);
+        assert_eq!(interior_points(&triangle), 1);
+    }
+
+    #[test]
+    fn test_locate_point() {
+        // an L shape
+        let shape = polygon(&[(0, 0), (6, 0), (6, 2), (2, 2), (2, 6), (0, 6)]);
+
+        for (point, expected) in [
+            ((1, 1), PointLocation::Inside),
+            ((1, 5), PointLocation::Inside),
+            ((5, 1), PointLocation::Inside),
+            ((0, 3), PointLocation::Boundary),
+            ((6, 2), PointLocation::Boundary),
+            ((4, 2), PointLocation::Boundary),
+            ((2, 4), PointLocation::Boundary),
+            ((3, 3), PointLocation::Outside),
+            ((7, 1), PointLocation::Outside),
+            ((-1, 0), PointLocation::Outside),
+            ((3, 6), PointLocation::Outside),
+        ] {
+            let point = Point::new(point.0, point.1);
+            assert_eq!(locate_point(&point, &shape), expected, "{point:?}");
+        }
+
+        let diamond = polygon(&[(0, -2), (2, 0), (0, 2), (-2, 0)]);
+        assert_eq!(
+            locate_point(&Point::new(0, 0), &diamond),
+            PointLocation::Inside
+        );
+        assert_eq!(
+            locate_point(&Point::new(1, 1), &diamond),
+            PointLocation::Boundary
+        );
+        assert_eq!(
+            locate_point(&Point::new(2, 1), &diamond),
+            PointLocation::Outside
+        );
+        assert_eq!(
+            locate_point(&Point::new(-2, 2), &diamond),
+            PointLocation::Outside
+        );
+    }
+
+    #[test]
+    fn test_rectangle_in_rectilinear_polygon() {
+        // a U shape, the notch is x 2..4, y 2..6
+        let shape = polygon(&[
+            (0, 0),
+            (6, 0),
+            (6, 6),
+            (4, 6),
+            (4, 2),
+            (2, 2),
+            (2, 6),
+            (0, 6),
+        ]);
+
+        for (a, b, expected) in [
+            ((0, 0), (6, 2), true),
+            ((0, 0), (2, 6), true),
+            ((4, 6), (6, 0), true),
+            ((1, 1), (1, 1), true),
+            ((0, 0), (6, 6), false),
+            ((0, 2), (6, 4), false),
+            ((2, 2), (4, 6), false),
+            ((2, 6), (4, 6), false),
+            ((2, 2), (4, 2), true),
+            ((0, 6), (6, 6), false),
+            ((1, 0), (1, 6), true),
+            ((3, 0), (3, 6), false),
+        ] {
+            let (a, b) = (Point::new(a.0, a.1), Point::new(b.0, b.1));
+            assert_eq!(
+                rectangle_in_rectilinear_polygon(&a, &b, &shape),
+                expected,
+                "{a:?} {b:?}"
+            );
+        }
+
+        // a flat rectangle along the top edge runs across the mouth of the notch
+        let notched = polygon(&[
+            (0, 0),
+            (8, 0),
+            (8, 6),
+            (3, 6),
+            (3, 2),
+            (1, 2),
+            (1, 6),
+            (0, 6),
+        ]);
+        let (a, b) = (Point::new(0, 6), Point::new(8, 6));
+        assert!(!rectangle_in_rectilinear_polygon(&a, &b, &notched));
+        let (a, b) = (Point::new(3, 6), Point::new(8, 6));
+        assert!(rectangle_in_rectilinear_polygon(&a, &b, &notched));
+    }
+}
@@ -0,0 +1,259 @@
+use std::collections::HashSet;
+
+use super::Graph;
+
+// Cliques and colourings treat the graph as undirected, an edge stored either way joins its
+// two nodes. Self loops are ignored.
+
+#[allow(dead_code)]
+impl<N, E> Graph<N, E> {
+    fn neighbour_sets(&self) -> Vec<HashSet<usize>> {
+        let mut sets = vec![HashSet::new(); self.len()];
+
+        for (from, to, _) in self.edges() {
+            if from != to {
+                sets[from].insert(to);
+                sets[to].insert(from);
+            }
+        }
+
+        sets
+    }
+
+    // Every set of three mutually connected nodes, each with its ids in increasing order.
+    pub fn triangles(&self) -> Vec<[usize; 3]> {
+        self.triangles_containing(|_| true)
+    }
+
+    // The triangles with at least one node for which `filter` holds.
+    pub fn triangles_containing<F>(&self, filter: F) -> Vec<[usize; 3]>
+    where
+        F: Fn(usize) -> bool,
+    {
+        let neighbours = self.neighbour_sets();
+        let mut triangles = vec![];
+
+        for a in 0..self.len() {
+            let mut higher = neighbours[a]
+                .iter()
+                .filter(|b| **b > a)
+                .copied()
+                .collect::<Vec<_>>();
+            higher.sort();
+
+            for (i, b) in higher.iter().enumerate() {
+                for c in higher[i + 1..].iter() {
+                    if neighbours[*b].contains(c) && [a, *b, *c].iter().any(|node| filter(*node)) {
+                        triangles.push([a, *b, *c]);
+                    }
+                }
+            }
+        }
+
+        triangles
+    }
+
+    // Every clique that can't be grown any further, by Bron-Kerbosch with pivoting. Each
+    // clique is sorted by id.
+    pub fn maximal_cliques(&self) -> Vec<Vec<usize>> {
+        let neighbours = self.neighbour_sets();
+        let mut cliques = vec![];
+
+        bron_kerbosch(
+            &neighbours,
+            &mut vec![],
+            (0..self.len()).collect(),
+            HashSet::new(),
+            &mut cliques,
+        );
+
+        for clique in cliques.iter_mut() {
+            clique.sort();
+        }
+        cliques.sort();
+
+        cliques
+    }
+
+    // The largest clique, the first by id if there is a tie.
+    pub fn maximum_clique(&self) -> Vec<usize> {
+        self.maximal_cliques()
+            .into_iter()
+            .rev()
+            .max_by_key(|clique| clique.len())
+            .unwrap_or_default()
+    }
+
+    // The names of `nodes` sorted and joined with commas, a canonical way to write a group.
+    pub fn canonical_names(&self, nodes: &[usize]) -> String {
+        let mut names = nodes
+            .iter()
+            .map(|node| self.name(*node))
+            .collect::<Vec<_>>();
+        names.sort();
+
+        names.join(",")
+    }
+
+    // A colour for each node so that neighbours never share one, greedily colouring the most
+    // connected nodes first (Welsh-Powell). Not always the fewest colours, but close.
+    pub fn greedy_colouring(&self) -> Vec<usize> {
+        let neighbours = self.neighbour_sets();
+
+        let mut order = (0..self.len()).collect::<Vec<_>>();
+        order.sort_by_key(|node| std::cmp::Reverse(neighbours[*node].len()));
+
+        let mut colours: Vec<Option<usize>> = vec![None; self.len()];
+        for node in order {
+            let used = neighbours[node]
+                .iter()
+                .filter_map(|other| colours[*other])
+                .collect::<HashSet<_>>();
+
+            colours[node] = (0..).find(|colour| !used.contains(colour));
+        }
+
+        colours.into_iter().map(Option::unwrap).collect()
+    }
+}
+
+fn bron_kerbosch(
+    neighbours: &[HashSet<usize>],
+    clique: &mut Vec<usize>,
+    mut candidates: HashSet<usize>,
+    mut excluded: HashSet<usize>,
+    cliques: &mut Vec<Vec<usize>>,
+) {
+    if candidates.is_empty() {
+        if excluded.is_empty() {
+            cliques.push(clique.clone());
+        }
+        return;
+    }
+
+    // any maximal clique has a node outside the pivot's neighbours, so only branch on those
+    let pivot = *candidates
+        .union(&excluded)
+        .max_by_key(|node| {
+            neighbours[**node]
+                .iter()
+                .filter(|n| candidates.contains(n))
+                .count()
+        })
+        .unwrap();
+
+    let branches = candidates
+        .difference(&neighbours[pivot])
+        .copied()
+        .collect::<Vec<_>>();
+
+    for node in branches {
+        clique.push(node);
+        bron_kerbosch(
+            neighbours,
+            clique,
+            candidates
+                .intersection(&neighbours[node])
+                .copied()
+                .collect(),
+            excluded.intersection(&neighbours[node]).copied().collect(),
+            cliques,
+        );
+        clique.pop();
+
+        candidates.remove(&node);
+        excluded.insert(node);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn graph(edges: &[(&str, &str)]) -> Graph {
+        let mut graph = Graph::new();
+
+        for (a, b) in edges {
+            let (a, b) = (graph.node_id(a), graph.node_id(b));
+            graph.add_edge(a, b, ());
+        }
+
+        graph
+    }
+
+    #[test]
+    fn test_triangles() {
+        // a square with one diagonal, and a tail
+        let graph = graph(&[
+            ("a", "b"),
+            ("b", "c"),
+            ("c", "d"),
+            ("d", "a"),
+            ("a", "c"),
+            ("d", "e"),
+        ]);
+        let id = |name| graph.id(name).unwrap();
+
+        let names = |triangles: Vec<[usize; 3]>| {
+            triangles
+                .iter()
+                .map(|triangle| graph.canonical_names(triangle))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(names(graph.triangles()), ["a,b,c", "a,c,d"]);
+        assert_eq!(
+            names(graph.triangles_containing(|node| node == id("d"))),
+            ["a,c,d"]
+        );
+        assert!(
+            graph
+                .triangles_containing(|node| node == id("e"))
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_cliques() {
+        // a four node clique with a triangle hanging off it
+        let graph = graph(&[
+            ("a", "b"),
+            ("a", "c"),
+            ("a", "d"),
+            ("b", "c"),
+            ("b", "d"),
+            ("c", "d"),
+            ("d", "e"),
+            ("e", "f"),
+            ("f", "d"),
+            ("g", "g"),
+        ]);
+
+        let cliques = graph
+            .maximal_cliques()
+            .iter()
+            .map(|clique| graph.canonical_names(clique))
+            .collect::<Vec<_>>();
+        assert_eq!(cliques, ["a,b,c,d", "d,e,f", "g"]);
+
+        assert_eq!(graph.canonical_names(&graph.maximum_clique()), "a,b,c,d");
+    }
+
+    #[test]
+    fn test_greedy_colouring() {
+        // an odd cycle needs three colours
+        let graph = graph(&[("a", "b"), ("b", "c"), ("c", "d"), ("d", "e"), ("e", "a")]);
+        let colours = graph.greedy_colouring();
+
+        for (from, to, _) in graph.edges() {
+            assert_ne!(colours[from], colours[to]);
+        }
+        assert_eq!(colours.iter().max(), Some(&2));
+
+        assert_eq!(
+            Graph::<(), ()>::new().greedy_colouring(),
+            Vec::<usize>::new()
+        );
+    }
+}
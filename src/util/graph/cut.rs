@@ -0,0 +1,290 @@
+use std::collections::VecDeque;
+
+use super::Graph;
+
+// A split of the nodes into two sides, along with the total weight of the edges between them.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Cut {
+    pub weight: i64,
+    // true for the nodes on the first side, which holds the source of a flow.
+    pub side: Vec<bool>,
+}
+
+#[allow(dead_code)]
+impl Cut {
+    pub fn partition_sizes(&self) -> (usize, usize) {
+        let first = self.side.iter().filter(|side| **side).count();
+
+        (first, self.side.len() - first)
+    }
+
+    // The edges that cross from the first side to the second.
+    pub fn edges<N, E>(&self, graph: &Graph<N, E>) -> Vec<(usize, usize)> {
+        graph
+            .edges()
+            .filter(|(from, to, _)| self.side[*from] && !self.side[*to])
+            .map(|(from, to, _)| (from, to))
+            .collect()
+    }
+}
+
+// One direction of a residual edge, `reverse` is the index of its twin in the target's list.
+#[derive(Debug, Clone)]
+struct Arc {
+    to: usize,
+    capacity: i64,
+    reverse: usize,
+}
+
+#[allow(dead_code)]
+impl<N, E> Graph<N, E> {
+    // The maximum flow from `source` to `sink` by Edmonds-Karp, given as the minimum cut that
+    // limits it. Edges are directed, an undirected edge carries flow either way.
+    pub fn min_cut<F>(&self, source: usize, sink: usize, capacity: F) -> Cut
+    where
+        F: Fn(&E) -> i64,
+    {
+        let mut residual: Vec<Vec<Arc>> = vec![vec![]; self.len()];
+
+        for (from, to, edge) in self.edges() {
+            let (forward, backward) = (residual[from].len(), residual[to].len());
+            residual[from].push(Arc {
+                to,
+                capacity: capacity(edge),
+                reverse: backward + usize::from(from == to),
+            });
+            residual[to].push(Arc {
+                to: from,
+                capacity: 0,
+                reverse: forward,
+            });
+        }
+
+        let mut flow = 0;
+
+        loop {
+            // shortest augmenting path, remembering the arc used to reach each node
+            let mut came_by: Vec<Option<(usize, usize)>> = vec![None; self.len()];
+            let mut queue = VecDeque::from([source]);
+            let mut seen = vec![false; self.len()];
+            seen[source] = true;
+
+            while let Some(node) = queue.pop_front() {
+                for (index, arc) in residual[node].iter().enumerate() {
+                    if arc.capacity > 0 && !seen[arc.to] {
+                        seen[arc.to] = true;
+                        came_by[arc.to] = Some((node, index));
+                        queue.push_back(arc.to);
+                    }
+                }
+            }
+
+            if !seen[sink] {
+                return Cut {
+                    weight: flow,
+                    side: seen,
+                };
+            }
+
+            let mut bottleneck = i64::MAX;
+            let mut node = sink;
+            while let Some((from, index)) = came_by[node] {
+                bottleneck = bottleneck.min(residual[from][index].capacity);
+                node = from;
+            }
+
+            let mut node = sink;
+            while let Some((from, index)) = came_by[node] {
+                residual[from][index].capacity -= bottleneck;
+                let reverse = residual[from][index].reverse;
+                residual[node][reverse].capacity += bottleneck;
+                node = from;
+            }
+
+            flow += bottleneck;
+        }
+    }
+
+    // The lightest cut over all ways of splitting the graph in two, by Stoer-Wagner in
+    // O(n^3). Edges are taken as undirected, an edge stored both ways counts once. None if
+    // there are fewer than two nodes.
+    pub fn stoer_wagner<F>(&self, weight: F) -> Option<Cut>
+    where
+        F: Fn(&E) -> i64,
+    {
+        let n = self.len();
+        if n < 2 {
+            return None;
+        }
+
+        let mut directed = vec![vec![0; n]; n];
+        for (from, to, edge) in self.edges() {
+            if from != to {
+                directed[from][to] += weight(edge);
+            }
+        }
+
+        let mut weights = vec![vec![0; n]; n];
+        for a in 0..n {
+            for b in 0..n {
+                weights[a][b] = directed[a][b].max(directed[b][a]);
+            }
+        }
+
+        // the original nodes merged into each remaining one
+        let mut merged = (0..n).map(|node| vec![node]).collect::<Vec<_>>();
+        let mut active = (0..n).collect::<Vec<_>>();
+        let mut best: Option<(i64, Vec<usize>)> = None;
+
+        while active.len() > 1 {
+            // maximum adjacency order, the last two added are the most tightly connected
+            let mut added = vec![false; n];
+            let mut connection = vec![0; n];
+            let (mut previous, mut last) = (active[0], active[0]);
+
+            for _ in 0..active.len() {
+                let next = *active
+                    .iter()
+                    .filter(|node| !added[**node])
+                    .max_by_key(|node| connection[**node])
+                    .unwrap();
+
+                added[next] = true;
+                (previous, last) = (last, next);
+
+                for node in active.iter() {
+                    connection[*node] += weights[next][*node];
+                }
+            }
+
+            // cutting `last` off on its own is the best cut separating it from `previous`
+            let cut_of_phase = active
+                .iter()
+                .filter(|node| **node != last)
+                .map(|node| weights[last][*node])
+                .sum::<i64>();
+
+            if best
+                .as_ref()
+                .is_none_or(|(weight, _)| cut_of_phase < *weight)
+            {
+                best = Some((cut_of_phase, merged[last].clone()));
+            }
+
+            let absorbed = std::mem::take(&mut merged[last]);
+            merged[previous].extend(absorbed);
+            let last_weights = weights[last].clone();
+            for (node, weight) in last_weights.iter().enumerate() {
+                weights[previous][node] += weight;
+                weights[node][previous] = weights[previous][node];
+            }
+            active.retain(|node| *node != last);
+        }
+
+        let (weight, group) = best?;
+        let mut side = vec![true; n];
+        for node in group {
+            side[node] = false;
+        }
+
+        // keep node 0 on the first side so the answer doesn't depend on the merge order
+        if !side[0] {
+            side.iter_mut().for_each(|side| *side = !*side);
+        }
+
+        Some(Cut { weight, side })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn undirected(edges: &[(&str, &str, i64)]) -> Graph<(), i64> {
+        let mut graph = Graph::new();
+
+        for (a, b, weight) in edges {
+            let (a, b) = (graph.node_id(a), graph.node_id(b));
+            graph.add_undirected_edge(a, b, *weight);
+        }
+
+        graph
+    }
+
+    #[test]
+    fn test_min_cut() {
+        // two triangles joined by a single light bridge
+        let graph = undirected(&[
+            ("a", "b", 3),
+            ("b", "c", 3),
+            ("c", "a", 3),
+            ("c", "d", 1),
+            ("d", "e", 3),
+            ("e", "f", 3),
+            ("f", "d", 3),
+        ]);
+        let id = |name| graph.id(name).unwrap();
+
+        let cut = graph.min_cut(id("a"), id("f"), |w| *w);
+        assert_eq!(cut.weight, 1);
+        assert_eq!(cut.partition_sizes(), (3, 3));
+        assert_eq!(cut.edges(&graph), [(id("c"), id("d"))]);
+
+        let cut = graph.min_cut(id("a"), id("b"), |w| *w);
+        assert_eq!(cut.weight, 6);
+    }
+
+    #[test]
+    fn test_min_cut_directed() {
+        let mut graph = Graph::<(), i64>::new();
+        let (s, a, b, t) = (
+            graph.node_id("s"),
+            graph.node_id("a"),
+            graph.node_id("b"),
+            graph.node_id("t"),
+        );
+        graph.add_edge(s, a, 10);
+        graph.add_edge(s, b, 5);
+        graph.add_edge(a, b, 15);
+        graph.add_edge(a, t, 4);
+        graph.add_edge(b, t, 10);
+
+        assert_eq!(graph.min_cut(s, t, |w| *w).weight, 14);
+        assert_eq!(graph.min_cut(t, s, |w| *w).weight, 0);
+    }
+
+    #[test]
+    fn test_stoer_wagner() {
+        // the example from the Stoer-Wagner paper, its minimum cut weighs 4
+        let graph = undirected(&[
+            ("1", "2", 2),
+            ("1", "5", 3),
+            ("2", "3", 3),
+            ("2", "5", 2),
+            ("2", "6", 2),
+            ("3", "4", 4),
+            ("3", "7", 2),
+            ("4", "7", 2),
+            ("4", "8", 2),
+            ("5", "6", 3),
+            ("6", "7", 1),
+            ("7", "8", 3),
+        ]);
+
+        let cut = graph.stoer_wagner(|w| *w).unwrap();
+        assert_eq!(cut.weight, 4);
+        assert_eq!(cut.partition_sizes(), (4, 4));
+
+        let names = |first: bool| {
+            (0..graph.len())
+                .filter(|node| cut.side[*node] == first)
+                .map(|node| graph.name(node))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names(true), ["1", "2", "5", "6"]);
+        assert_eq!(names(false), ["3", "4", "7", "8"]);
+
+        assert_eq!(Graph::<(), i64>::new().stoer_wagner(|w| *w), None);
+    }
+}
@@ -0,0 +1,370 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::ops::Add;
+use std::str::FromStr;
+
+use anyhow::{Result, anyhow};
+
+pub mod cliques;
+pub mod cut;
+
+// Hands out dense ids for names, in the order they are first seen, so graph algorithms can
+// work with plain indexes and vectors instead of hashing strings.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Interner {
+    ids: HashMap<String, usize>,
+    names: Vec<String>,
+}
+
+#[allow(dead_code)]
+impl Interner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn intern(&mut self, name: &str) -> usize {
+        if let Some(id) = self.ids.get(name) {
+            return *id;
+        }
+
+        let id = self.names.len();
+        self.ids.insert(name.to_string(), id);
+        self.names.push(name.to_string());
+
+        id
+    }
+
+    pub fn get(&self, name: &str) -> Option<usize> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn name(&self, id: usize) -> &str {
+        &self.names[id]
+    }
+
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+}
+
+// Named nodes carrying data `N`, joined by directed edges carrying `E`. An undirected edge is
+// stored as a pair of directed ones.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Graph<N = (), E = ()> {
+    names: Interner,
+    nodes: Vec<N>,
+    adjacency: Vec<Vec<(usize, E)>>,
+}
+
+impl<N, E> Default for Graph<N, E> {
+    fn default() -> Self {
+        Self {
+            names: Interner::default(),
+            nodes: vec![],
+            adjacency: vec![],
+        }
+    }
+}
+
+#[allow(dead_code)]
+impl<N, E> Graph<N, E> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Adds a node, or replaces the data of the node already using `name`.
+    pub fn add_node(&mut self, name: &str, data: N) -> usize {
+        let id = self.names.intern(name);
+
+        if id == self.nodes.len() {
+            self.nodes.push(data);
+            self.adjacency.push(vec![]);
+        } else {
+            self.nodes[id] = data;
+        }
+
+        id
+    }
+
+    // The id of `name`, adding it with default data if it is new.
+    pub fn node_id(&mut self, name: &str) -> usize
+    where
+        N: Default,
+    {
+        match self.names.get(name) {
+            Some(id) => id,
+            None => self.add_node(name, N::default()),
+        }
+    }
+
+    pub fn id(&self, name: &str) -> Option<usize> {
+        self.names.get(name)
+    }
+
+    pub fn name(&self, id: usize) -> &str {
+        self.names.name(id)
+    }
+
+    pub fn names(&self) -> &[String] {
+        self.names.names()
+    }
+
+    pub fn node(&self, id: usize) -> &N {
+        &self.nodes[id]
+    }
+
+    pub fn node_mut(&mut self, id: usize) -> &mut N {
+        &mut self.nodes[id]
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn add_edge(&mut self, from: usize, to: usize, edge: E) {
+        self.adjacency[from].push((to, edge));
+    }
+
+    pub fn add_undirected_edge(&mut self, a: usize, b: usize, edge: E)
+    where
+        E: Clone,
+    {
+        self.add_edge(a, b, edge.clone());
+        self.add_edge(b, a, edge);
+    }
+
+    pub fn neighbours(&self, id: usize) -> impl Iterator<Item = (usize, &E)> + '_ {
+        self.adjacency[id].iter().map(|(to, edge)| (*to, edge))
+    }
+
+    pub fn edge(&self, from: usize, to: usize) -> Option<&E> {
+        self.adjacency[from]
+            .iter()
+            .find(|(id, _)| *id == to)
+            .map(|(_, edge)| edge)
+    }
+
+    // Every edge as (from, to, edge), undirected edges show up once each way.
+    pub fn edges(&self) -> impl Iterator<Item = (usize, usize, &E)> + '_ {
+        self.adjacency
+            .iter()
+            .enumerate()
+            .flat_map(|(from, edges)| edges.iter().map(move |(to, edge)| (from, *to, edge)))
+    }
+
+    pub fn adjacency_list(&self, id: usize) -> &[(usize, E)] {
+        &self.adjacency[id]
+    }
+
+    pub fn adjacency_matrix(&self) -> Vec<Vec<Option<E>>>
+    where
+        E: Clone,
+    {
+        let mut matrix = vec![vec![None; self.len()]; self.len()];
+
+        for (from, to, edge) in self.edges() {
+            matrix[from][to] = Some(edge.clone());
+        }
+
+        matrix
+    }
+
+    // The shortest distance between every pair of nodes, None where there is no path. The
+    // weight of each edge comes from `weight`, and must not make a negative cycle.
+    pub fn floyd_warshall<W, F>(&self, weight: F) -> Vec<Vec<Option<W>>>
+    where
+        W: Copy + Ord + Default + Add<Output = W>,
+        F: Fn(&E) -> W,
+    {
+        let n = self.len();
+        let mut distances = vec![vec![None; n]; n];
+
+        for (node, row) in distances.iter_mut().enumerate() {
+            row[node] = Some(W::default());
+        }
+
+        for (from, to, edge) in self.edges() {
+            let w = weight(edge);
+            if distances[from][to].is_none_or(|d| w < d) {
+                distances[from][to] = Some(w);
+            }
+        }
+
+        for via in 0..n {
+            let from_via = distances[via].clone();
+
+            for row in distances.iter_mut() {
+                let Some(first) = row[via] else {
+                    continue;
+                };
+
+                for (distance, second) in row.iter_mut().zip(from_via.iter()) {
+                    let Some(second) = second else {
+                        continue;
+                    };
+
+                    let through = first + *second;
+                    if distance.is_none_or(|d| through < d) {
+                        *distance = Some(through);
+                    }
+                }
+            }
+        }
+
+        distances
+    }
+}
+
+#[allow(dead_code)]
+impl<N, E> Graph<N, E>
+where
+    N: Default,
+    E: FromStr + Clone,
+    E::Err: Display,
+{
+    // Undirected weighted edges written one per line as "A to B = 5".
+    pub fn parse_weighted_edges(lines: &[&str]) -> Result<Self> {
+        let mut graph = Self::new();
+
+        for line in lines {
+            let (nodes, weight) = line
+                .split_once(" = ")
+                .ok_or_else(|| anyhow!("Graph::parse: expected `=` in `{line}`"))?;
+            let (a, b) = nodes
+                .split_once(" to ")
+                .ok_or_else(|| anyhow!("Graph::parse: expected `to` in `{line}`"))?;
+            let weight = weight.trim().parse::<E>().map_err(|e| {
+                anyhow!("Graph::parse: could not parse `{weight}` in `{line}`: {e}")
+            })?;
+
+            let (a, b) = (graph.node_id(a.trim()), graph.node_id(b.trim()));
+            graph.add_undirected_edge(a, b, weight);
+        }
+
+        Ok(graph)
+    }
+}
+
+#[allow(dead_code)]
+impl<N> Graph<N, ()>
+where
+    N: Default,
+{
+    // Directed edges written one node per line as "A: B C D", from A to each of B, C and D.
+    pub fn parse_adjacency_lists(lines: &[&str]) -> Result<Self> {
+        let mut graph = Self::new();
+
+        for line in lines {
+            let (from, targets) = line
+                .split_once(':')
+                .ok_or_else(|| anyhow!("Graph::parse: expected `:` in `{line}`"))?;
+            let from = graph.node_id(from.trim());
+
+            for to in targets.split_whitespace() {
+                let to = graph.node_id(to);
+                graph.add_edge(from, to, ());
+            }
+        }
+
+        Ok(graph)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_interner() {
+        let mut names = Interner::new();
+
+        assert_eq!(names.intern("London"), 0);
+        assert_eq!(names.intern("Dublin"), 1);
+        assert_eq!(names.intern("London"), 0);
+        assert_eq!(names.get("Dublin"), Some(1));
+        assert_eq!(names.get("Belfast"), None);
+        assert_eq!(names.name(1), "Dublin");
+        assert_eq!(names.len(), 2);
+    }
+
+    #[test]
+    fn test_parse_weighted_edges() {
+        #[rustfmt::skip]
+        let input = [
+            "London to Dublin = 464",
+            "London to Belfast = 518",
+            "Dublin to Belfast = 141",
+        ];
+
+        let graph = Graph::<(), u32>::parse_weighted_edges(&input).unwrap();
+        let (london, dublin, belfast) = (0, 1, 2);
+
+        assert_eq!(graph.names(), ["London", "Dublin", "Belfast"]);
+        assert_eq!(graph.edge(dublin, london), Some(&464));
+        assert_eq!(graph.edge(belfast, dublin), Some(&141));
+        assert_eq!(graph.edges().count(), 6);
+        assert_eq!(
+            graph.adjacency_matrix(),
+            [
+                [None, Some(464), Some(518)],
+                [Some(464), None, Some(141)],
+                [Some(518), Some(141), None]
+            ]
+        );
+
+        assert!(Graph::<(), u32>::parse_weighted_edges(&["London to Dublin"]).is_err());
+        assert!(Graph::<(), u32>::parse_weighted_edges(&["London Dublin = 1"]).is_err());
+        assert!(Graph::<(), u32>::parse_weighted_edges(&["London to Dublin = x"]).is_err());
+    }
+
+    #[test]
+    fn test_floyd_warshall() {
+        #[rustfmt::skip]
+        let input = [
+            "you: bbb ccc",
+            "bbb: ddd",
+            "ccc: ddd out",
+            "ddd: out",
+        ];
+
+        let graph = Graph::<(), ()>::parse_adjacency_lists(&input).unwrap();
+        let id = |name| graph.id(name).unwrap();
+
+        assert_eq!(graph.len(), 5);
+        assert_eq!(
+            graph
+                .neighbours(id("you"))
+                .map(|(to, _)| to)
+                .collect::<Vec<_>>(),
+            [id("bbb"), id("ccc")]
+        );
+
+        let distances = graph.floyd_warshall(|_| 1);
+        assert_eq!(distances[id("you")][id("out")], Some(2));
+        assert_eq!(distances[id("bbb")][id("out")], Some(2));
+        assert_eq!(distances[id("out")][id("you")], None);
+        assert_eq!(distances[id("ddd")][id("ddd")], Some(0));
+
+        // a longer path with lighter edges wins
+        let mut graph = Graph::<(), i64>::new();
+        let (a, b, c) = (graph.node_id("a"), graph.node_id("b"), graph.node_id("c"));
+        graph.add_undirected_edge(a, c, 10);
+        graph.add_undirected_edge(a, b, 3);
+        graph.add_undirected_edge(b, c, 4);
+
+        assert_eq!(graph.floyd_warshall(|w| *w)[c][a], Some(7));
+    }
+}
@@ -1,9 +1,13 @@
 use anyhow::{Result, anyhow};
 use std::cmp::{max, min};
+use std::collections::HashMap;
+use std::convert::Infallible;
 use std::fmt::Display;
+use std::hash::Hash;
 use std::ops::{Deref, DerefMut, Index, IndexMut};
 use std::{collections::HashSet, fmt};
 
+use super::grid_render::{Color, GridRenderer, Style};
 pub use super::point::{GridDirection, Point};
 
 #[derive(PartialEq, Eq, Clone, Hash)]
@@ -35,20 +39,71 @@ where
         }
     }
 
-    pub fn parse(lines: &[&str], parse_fn: fn(char) -> T) -> Self {
-        let width = lines[0].len();
+    pub fn parse<F: FnMut(char) -> T>(lines: &[&str], mut parse_fn: F) -> Self {
+        Self::try_parse(lines, |ch| Ok::<T, Infallible>(parse_fn(ch)))
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    // Fails if the lines are not all the same width or if `parse_fn` fails on any character.
+    pub fn try_parse<F, E>(lines: &[&str], parse_fn: F) -> Result<Self>
+    where
+        F: FnMut(char) -> std::result::Result<T, E>,
+        E: Display,
+    {
+        Self::try_parse_rows(lines, None, parse_fn)
+    }
+
+    // Like `try_parse`, but rows shorter than the longest one are padded with `pad`.
+    pub fn try_parse_padded<F, E>(lines: &[&str], pad: T, parse_fn: F) -> Result<Self>
+    where
+        F: FnMut(char) -> std::result::Result<T, E>,
+        E: Display,
+    {
+        Self::try_parse_rows(lines, Some(pad), parse_fn)
+    }
+
+    fn try_parse_rows<F, E>(lines: &[&str], pad: Option<T>, mut parse_fn: F) -> Result<Self>
+    where
+        F: FnMut(char) -> std::result::Result<T, E>,
+        E: Display,
+    {
         let height = lines.len();
-        let spaces = lines
-            .join("")
-            .chars()
-            .map(|ch| parse_fn(ch))
-            .collect::<Vec<T>>();
+        let width = lines
+            .iter()
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or(0);
 
-        Self {
+        let mut spaces = Vec::with_capacity(width * height);
+
+        for (y, line) in lines.iter().enumerate() {
+            let mut row_width = 0;
+
+            for (x, ch) in line.chars().enumerate() {
+                let val = parse_fn(ch).map_err(|e| {
+                    anyhow!("Grid::parse: could not parse `{ch}` at x: {x}, y: {y}: {e}")
+                })?;
+
+                spaces.push(val);
+                row_width += 1;
+            }
+
+            if row_width < width {
+                let Some(pad) = &pad else {
+                    return Err(anyhow!(
+                        "Grid::parse: row {y} has width {row_width}, expected {width}"
+                    ));
+                };
+
+                spaces.extend(std::iter::repeat_n(pad.clone(), width - row_width));
+            }
+        }
+
+        Ok(Self {
             spaces,
             width,
             height,
-        }
+        })
     }
 
     pub fn get(&self, point: &Point) -> Option<&T> {
@@ -144,6 +199,12 @@ where
     }
 
     pub fn rotate_clockwise(&mut self) {
+        // an empty grid has no cell to seed the new one with, only its shape changes
+        if self.width == 0 || self.height == 0 {
+            std::mem::swap(&mut self.width, &mut self.height);
+            return;
+        }
+
         let r_width = self.height;
         let r_height = self.width;
 
@@ -161,6 +222,12 @@ where
     }
 
     pub fn rotate_counter_clockwise(&mut self) {
+        // an empty grid has no cell to seed the new one with, only its shape changes
+        if self.width == 0 || self.height == 0 {
+            std::mem::swap(&mut self.width, &mut self.height);
+            return;
+        }
+
         let r_width = self.height;
         let r_height = self.width;
 
@@ -177,6 +244,95 @@ where
         std::mem::swap(self, &mut rotated);
     }
 
+    pub fn transpose(&mut self) {
+        // an empty grid has no cell to seed the new one with, only its shape changes
+        if self.width == 0 || self.height == 0 {
+            std::mem::swap(&mut self.width, &mut self.height);
+            return;
+        }
+
+        let mut transposed = Self::new(self.height, self.width, self[(0, 0)].clone());
+
+        for (y, line) in self.spaces[..].chunks(self.width).enumerate() {
+            for (x, val) in line.iter().enumerate() {
+                transposed[(y, x)] = val.clone();
+            }
+        }
+
+        std::mem::swap(self, &mut transposed);
+    }
+
+    pub fn flip_horizontal(&mut self) {
+        if self.width == 0 {
+            return;
+        }
+
+        for line in self.spaces.chunks_mut(self.width) {
+            line.reverse();
+        }
+    }
+
+    pub fn flip_vertical(&mut self) {
+        if self.width == 0 {
+            return;
+        }
+
+        let mut flipped = Vec::with_capacity(self.spaces.len());
+
+        for line in self.spaces.chunks(self.width).rev() {
+            flipped.extend_from_slice(line);
+        }
+
+        self.spaces = flipped;
+    }
+
+    // All eight rotations and reflections, starting with the grid as is followed by its three
+    // clockwise rotations, then the horizontal mirror image and its three clockwise rotations.
+    pub fn orientations(&self) -> Vec<Self> {
+        let mut orientations = Vec::with_capacity(8);
+
+        let mut grid = self.clone();
+        for _ in 0..2 {
+            for _ in 0..4 {
+                orientations.push(grid.clone());
+                grid.rotate_clockwise();
+            }
+
+            grid.flip_horizontal();
+        }
+
+        orientations
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.spaces[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        self.spaces.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.spaces.chunks(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    pub fn sub_grid(&self, origin: Point, width: usize, height: usize) -> Option<SubGrid<'_, T>> {
+        if origin.x + width > self.width || origin.y + height > self.height {
+            return None;
+        }
+
+        Some(SubGrid {
+            grid: self,
+            origin,
+            width,
+            height,
+        })
+    }
+
     pub fn idx_point(&self, idx: usize) -> Point {
         let (x, y) = self.idx_xy(idx);
 
@@ -210,29 +366,12 @@ where
     }
 
     pub fn print_path(&self, path: &[Point]) {
-        let path_idxs = path
-            .iter()
-            .map(|p| self.point_idx(*p))
-            .collect::<HashSet<_>>();
-
-        let mut line = String::new();
-        for (i, v) in self.spaces.iter().enumerate() {
-            if path_idxs.get(&i).is_some() {
-                line.push_str(&format!("\x1b[1m\x1b[94m{}\x1b[0m\x1b[0m", v));
-            } else {
-                line.push_str(&format!("{}", v));
-            }
-
-            if i > 0 && (i + 1) % self.width == 0 {
-                println!("{}", line);
-                line.truncate(0);
-            }
-        }
+        GridRenderer::new(self)
+            .colour(true)
+            .path(path, Style::new().fg(Color::BrightBlue).bold())
+            .print();
 
         println!();
-
-        // println!("\x1b[94mTest\x1b[0m");
-        // println!("\x1b[1m\x1b[94mTest\x1b[0m\x1b[0m");
     }
 
     pub fn draw_horizontal_line(&mut self, start: &Point, end: &Point, to_draw: T) -> Result<()> {
@@ -253,6 +392,83 @@ where
         Ok(())
     }
 
+    pub fn neighbors4(&self, point: &Point) -> impl Iterator<Item = (Point, &T)> {
+        GridDirection::cardinal()
+            .into_iter()
+            .filter_map(move |direction| self.enumerate_direction(point, direction))
+    }
+
+    pub fn neighbors8(&self, point: &Point) -> impl Iterator<Item = (Point, &T)> {
+        GridDirection::all()
+            .into_iter()
+            .filter_map(move |direction| self.enumerate_direction(point, direction))
+    }
+
+    // Every point 4-connected to `seed` through cells matching `predicate`.
+    pub fn flood_fill<F: Fn(&T) -> bool>(&self, seed: &Point, predicate: F) -> HashSet<Point> {
+        let mut filled = HashSet::new();
+
+        if !self.get(seed).is_some_and(&predicate) {
+            return filled;
+        }
+
+        let mut to_visit = vec![*seed];
+        filled.insert(*seed);
+
+        while let Some(point) = to_visit.pop() {
+            for (neighbor, val) in self.neighbors4(&point) {
+                if predicate(val) && filled.insert(neighbor) {
+                    to_visit.push(neighbor);
+                }
+            }
+        }
+
+        filled
+    }
+
+    // Splits the grid into 4-connected regions of equal cells. The returned grid holds the index
+    // of the region each cell belongs to.
+    pub fn label_regions(&self) -> (Grid<usize>, Vec<Region>) {
+        let mut labels = Grid::new(self.width, self.height, usize::MAX);
+        let mut regions = vec![];
+
+        for idx in 0..self.spaces.len() {
+            if labels.spaces[idx] != usize::MAX {
+                continue;
+            }
+
+            let seed = self.idx_point(idx);
+            let value = &self.spaces[idx];
+            let points = self.flood_fill(&seed, |v| v == value);
+
+            for point in points.iter() {
+                labels[point] = regions.len();
+            }
+
+            regions.push(Region::new(points));
+        }
+
+        (labels, regions)
+    }
+
+    // Regions formed by cells matching `predicate`, cells that don't match belong to no region.
+    pub fn connected_components<F: Fn(&T) -> bool>(&self, predicate: F) -> Vec<Region> {
+        let mut visited = HashSet::new();
+        let mut regions = vec![];
+
+        for (idx, val) in self.spaces.iter().enumerate() {
+            let seed = self.idx_point(idx);
+
+            if predicate(val) && !visited.contains(&seed) {
+                let points = self.flood_fill(&seed, &predicate);
+                visited.extend(points.iter().copied());
+                regions.push(Region::new(points));
+            }
+        }
+
+        regions
+    }
+
     pub fn find_fn<F: Fn(&T) -> bool>(&self, to_find: F) -> Option<Point> {
         let mut location = None;
 
@@ -281,9 +497,225 @@ impl Grid<char> {
 #[allow(dead_code)]
 impl Grid<u32> {
     pub fn parse_u32(lines: &[&str]) -> Self {
-        Self::parse(lines, |n| {
-            n.to_digit(10)
-                .unwrap_or_else(|| panic!("unable to parse {} as digit", n))
+        Self::try_parse_u32(lines).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    pub fn try_parse_u32(lines: &[&str]) -> Result<Self> {
+        Self::try_parse(lines, |n| n.to_digit(10).ok_or("not a digit"))
+    }
+}
+
+#[allow(dead_code)]
+impl<T> Grid<T>
+where
+    T: std::fmt::Display + std::fmt::Debug + Clone + PartialEq + Eq + Hash,
+{
+    // Finds every cell holding one of `markers`, replacing it with `background`. Returns the
+    // positions of each marker in reading order.
+    pub fn extract_markers(&mut self, markers: &[T], background: T) -> HashMap<T, Vec<Point>> {
+        let mut positions: HashMap<T, Vec<Point>> = HashMap::new();
+
+        for idx in 0..self.spaces.len() {
+            if markers.contains(&self.spaces[idx]) {
+                let marker = std::mem::replace(&mut self.spaces[idx], background.clone());
+                positions
+                    .entry(marker)
+                    .or_default()
+                    .push(self.idx_point(idx));
+            }
+        }
+
+        positions
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Region {
+    pub points: HashSet<Point>,
+}
+
+#[allow(dead_code)]
+impl Region {
+    pub fn new(points: HashSet<Point>) -> Self {
+        Self { points }
+    }
+
+    pub fn area(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn contains(&self, point: &Point) -> bool {
+        self.points.contains(point)
+    }
+
+    fn is_outside(&self, point: &Point, direction: GridDirection) -> bool {
+        point
+            .get_adjacent(direction)
+            .is_none_or(|adjacent| !self.contains(&adjacent))
+    }
+
+    // Each unit edge between a region cell and the outside, as the cell and the direction the
+    // edge faces. Sorted so that the edges of each side are consecutive, for following the
+    // edges around the region see `outlines`.
+    pub fn edges(&self) -> Vec<(Point, GridDirection)> {
+        let mut edges = self
+            .points
+            .iter()
+            .flat_map(|point| {
+                GridDirection::cardinal()
+                    .into_iter()
+                    .filter(|direction| self.is_outside(point, *direction))
+                    .map(|direction| (*point, direction))
+            })
+            .collect::<Vec<_>>();
+
+        edges.sort_by_key(|(point, direction)| match direction {
+            GridDirection::Up | GridDirection::Down => (*direction as usize, point.y, point.x),
+            _ => (*direction as usize, point.x, point.y),
+        });
+
+        edges
+    }
+
+    // The edges in the order they are met walking around the region, one closed loop for the
+    // outside and one for each hole. The region is always on the right, so the outside is
+    // walked clockwise and holes counter clockwise. Cells that only touch at a corner are
+    // walked around together, so holes that meet at a corner stay separate loops.
+    pub fn outlines(&self) -> Vec<Vec<(Point, GridDirection)>> {
+        let mut remaining = self.edges().into_iter().collect::<HashSet<_>>();
+        let mut outlines = vec![];
+
+        for start in self.edges() {
+            if !remaining.remove(&start) {
+                continue;
+            }
+
+            let mut outline = vec![start];
+            let mut edge = self.next_edge(start);
+            while edge != start {
+                remaining.remove(&edge);
+                outline.push(edge);
+                edge = self.next_edge(edge);
+            }
+
+            outlines.push(outline);
+        }
+
+        outlines
+    }
+
+    // The edge after this one going clockwise around the outside of the region.
+    fn next_edge(&self, (point, facing): (Point, GridDirection)) -> (Point, GridDirection) {
+        let travel = facing.turn_clockwise_90();
+        let inside = |point: Option<Point>| point.filter(|point| self.contains(point));
+
+        let ahead = point.get_adjacent(travel);
+        let diagonal = ahead.and_then(|ahead| ahead.get_adjacent(facing));
+
+        if let Some(diagonal) = inside(diagonal) {
+            // the region carries on past the end of the edge, so it turns back around that cell
+            (diagonal, facing.turn_counter_clockwise_90())
+        } else if let Some(ahead) = inside(ahead) {
+            (ahead, facing)
+        } else {
+            // the edge turns around the corner of this cell
+            (point, travel)
+        }
+    }
+
+    pub fn perimeter(&self) -> usize {
+        self.edges().len()
+    }
+
+    // Number of straight fence sides, edges facing the same way on neighbouring cells are
+    // part of the same side.
+    pub fn sides(&self) -> usize {
+        self.edges()
+            .iter()
+            .filter(|(point, direction)| {
+                let previous = match direction {
+                    GridDirection::Up | GridDirection::Down => GridDirection::Left,
+                    _ => GridDirection::Up,
+                };
+
+                point.get_adjacent(previous).is_none_or(|previous| {
+                    !self.contains(&previous) || !self.is_outside(&previous, *direction)
+                })
+            })
+            .count()
+    }
+}
+
+// A borrowed rectangular window into a grid, indexed by points relative to its origin.
+pub struct SubGrid<'a, T> {
+    grid: &'a Grid<T>,
+    origin: Point,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl<T> Clone for SubGrid<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for SubGrid<'_, T> {}
+
+#[allow(dead_code)]
+impl<'a, T> SubGrid<'a, T>
+where
+    T: std::fmt::Display + std::fmt::Debug + Clone + PartialEq,
+{
+    pub fn get(&self, point: &Point) -> Option<&'a T> {
+        if point.x < self.width && point.y < self.height {
+            self.grid.get(&(self.origin + *point))
+        } else {
+            None
+        }
+    }
+
+    pub fn row(&self, y: usize) -> &'a [T] {
+        let start = self.grid.point_idx(self.origin + Point::new(0, y));
+
+        &self.grid.spaces[start..start + self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &'a [T]> {
+        let sub_grid = *self;
+
+        (0..self.height).map(move |y| sub_grid.row(y))
+    }
+
+    pub fn enumerate(&self) -> impl Iterator<Item = (Point, &'a T)> {
+        self.rows().enumerate().flat_map(|(y, line)| {
+            line.iter()
+                .enumerate()
+                .map(move |(x, val)| (Point::new(x, y), val))
+        })
+    }
+
+    pub fn to_grid(self) -> Grid<T> {
+        Grid::from_vec(
+            self.rows().flatten().cloned().collect(),
+            self.width,
+            self.height,
+        )
+    }
+}
+
+impl<T> Index<&Point> for SubGrid<'_, T>
+where
+    T: Clone + std::fmt::Debug + PartialEq + std::fmt::Display,
+{
+    type Output = T;
+
+    fn index(&self, index: &Point) -> &Self::Output {
+        self.get(index).unwrap_or_else(|| {
+            panic!(
+                "Could not get point {index:?}. SubGrid bounds width: {}, height: {}",
+                self.width, self.height
+            )
         })
     }
 }
@@ -414,25 +846,10 @@ where
 
 impl<T> Display for Grid<T>
 where
-    T: std::fmt::Display + std::fmt::Debug,
+    T: std::fmt::Display + std::fmt::Debug + Clone + PartialEq,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let mut whitespace = String::new();
-        whitespace.push_str("\n   ");
-
-        whitespace.push_str("   ");
-
-        let mut line = whitespace.to_string();
-        for (i, space) in self.spaces.iter().enumerate() {
-            // line.push_str(&format!("{:#?}, ", space));
-            line.push_str(&format!("{}", space));
-            if i > 0 && (i + 1) % self.width == 0 {
-                write!(f, "{line}")?;
-                line = whitespace.to_string();
-            }
-        }
-
-        Ok(())
+        GridRenderer::new(self).render_to(f)
     }
 }
 
@@ -574,4 +991,340 @@ mod tests {
 
         assert_eq!(grid, expected);
     }
+
+    #[test]
+    fn test_transpose() {
+        #[rustfmt::skip]
+        let mut grid = Grid::parse_char(&[
+            "abc",
+            "def",
+        ]);
+
+        #[rustfmt::skip]
+        let expected = Grid::parse_char(&[
+            "ad",
+            "be",
+            "cf",
+        ]);
+
+        grid.transpose();
+
+        assert_eq!(grid, expected);
+
+        let mut empty = Grid::new(3, 0, 'x');
+        empty.transpose();
+        assert_eq!((empty.width, empty.height), (0, 3));
+        empty.rotate_clockwise();
+        assert_eq!((empty.width, empty.height), (3, 0));
+        assert_eq!(Grid::new(0, 0, 'x').orientations().len(), 8);
+    }
+
+    #[test]
+    fn test_flips() {
+        #[rustfmt::skip]
+        let grid = Grid::parse_char(&[
+            "abc",
+            "def",
+        ]);
+
+        let mut flipped = grid.clone();
+        flipped.flip_horizontal();
+        assert_eq!(flipped, Grid::parse_char(&["cba", "fed"]));
+
+        let mut flipped = grid.clone();
+        flipped.flip_vertical();
+        assert_eq!(flipped, Grid::parse_char(&["def", "abc"]));
+
+        for (width, height) in [(0, 0), (0, 2), (2, 0)] {
+            let empty = Grid::new(width, height, 'x');
+
+            let mut flipped = empty.clone();
+            flipped.flip_horizontal();
+            flipped.flip_vertical();
+            assert_eq!(flipped, empty);
+        }
+    }
+
+    #[test]
+    fn test_orientations() {
+        #[rustfmt::skip]
+        let grid = Grid::parse_char(&[
+            "ab",
+            "cd",
+        ]);
+
+        let actual = grid.orientations();
+
+        #[rustfmt::skip]
+        let expected = [
+            ["ab", "cd"], ["ca", "db"], ["dc", "ba"], ["bd", "ac"],
+            ["ba", "dc"], ["db", "ca"], ["cd", "ab"], ["ac", "bd"],
+        ]
+        .map(|lines| Grid::parse_char(&lines));
+
+        assert_eq!(actual, expected);
+
+        let symmetric = Grid::parse_char(&["###", ".#.", "###"]);
+        let unique = symmetric.orientations().into_iter().collect::<HashSet<_>>();
+        assert_eq!(unique.len(), 2);
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        #[rustfmt::skip]
+        let grid = Grid::parse_char(&[
+            "abc",
+            "def",
+        ]);
+
+        let rows = grid.rows().map(String::from_iter).collect::<Vec<_>>();
+        assert_eq!(rows, ["abc", "def"]);
+
+        let columns = grid.columns().map(String::from_iter).collect::<Vec<_>>();
+        assert_eq!(columns, ["ad", "be", "cf"]);
+
+        assert_eq!(grid.row(1), &['d', 'e', 'f']);
+        assert_eq!(grid.column(2).collect::<String>(), "cf");
+    }
+
+    #[test]
+    fn test_sub_grid() {
+        #[rustfmt::skip]
+        let grid = Grid::parse_char(&[
+            "abcde",
+            "fghij",
+            "klmno",
+            "pqrst",
+        ]);
+
+        let sub_grid = grid.sub_grid(Point::new(1, 1), 3, 2).unwrap();
+
+        assert_eq!(sub_grid[&Point::new(0, 0)], 'g');
+        assert_eq!(sub_grid[&Point::new(2, 1)], 'n');
+        assert_eq!(sub_grid.get(&Point::new(3, 0)), None);
+        assert_eq!(sub_grid.get(&Point::new(0, 2)), None);
+        assert_eq!(sub_grid.to_grid(), Grid::parse_char(&["ghi", "lmn"]));
+        assert_eq!(
+            sub_grid
+                .enumerate()
+                .filter(|(_, ch)| **ch == 'm')
+                .map(|(p, _)| p)
+                .collect::<Vec<_>>(),
+            [Point::new(1, 1)]
+        );
+
+        assert!(grid.sub_grid(Point::new(3, 3), 3, 2).is_none());
+        assert!(grid.sub_grid(Point::new(2, 2), 3, 2).is_some());
+    }
+
+    #[test]
+    fn test_neighbors() {
+        #[rustfmt::skip]
+        let grid = Grid::parse_char(&[
+            "abc",
+            "def",
+            "ghi",
+        ]);
+
+        let actual = grid
+            .neighbors4(&Point::new(0, 0))
+            .map(|(_, v)| *v)
+            .collect::<String>();
+        assert_eq!(actual, "bd");
+
+        let actual = grid
+            .neighbors8(&Point::new(1, 1))
+            .map(|(_, v)| *v)
+            .collect::<String>();
+        assert_eq!(actual, "abcfihgd");
+
+        let actual = grid
+            .neighbors8(&Point::new(2, 2))
+            .map(|(p, _)| p)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            actual,
+            [Point::new(1, 1), Point::new(2, 1), Point::new(1, 2)]
+        );
+    }
+
+    #[test]
+    fn test_flood_fill() {
+        #[rustfmt::skip]
+        let grid = Grid::parse_char(&[
+            "..#..",
+            "..#..",
+            "###..",
+            "....#",
+        ]);
+
+        let filled = grid.flood_fill(&Point::new(0, 0), |ch| *ch == '.');
+        assert_eq!(filled.len(), 4);
+
+        let filled = grid.flood_fill(&Point::new(4, 0), |ch| *ch == '.');
+        assert_eq!(filled.len(), 10);
+        assert!(filled.contains(&Point::new(0, 3)));
+
+        let filled = grid.flood_fill(&Point::new(2, 0), |ch| *ch == '.');
+        assert!(filled.is_empty());
+    }
+
+    #[test]
+    fn test_label_regions() {
+        // 2024 day 12 example
+        #[rustfmt::skip]
+        let grid = Grid::parse_char(&[
+            "AAAA",
+            "BBCD",
+            "BBCC",
+            "EEEC",
+        ]);
+
+        let (labels, regions) = grid.label_regions();
+
+        assert_eq!(regions.len(), 5);
+        assert_eq!(labels[(0, 0)], labels[(3, 0)]);
+        assert_eq!(labels[(2, 1)], labels[(3, 3)]);
+        assert_ne!(labels[(2, 1)], labels[(3, 1)]);
+
+        let summary = regions
+            .iter()
+            .map(|r| (r.area(), r.perimeter(), r.sides()))
+            .collect::<Vec<_>>();
+
+        // A, B, C, D, E
+        assert_eq!(
+            summary,
+            [(4, 10, 4), (4, 8, 4), (4, 10, 8), (1, 4, 4), (3, 8, 4)]
+        );
+    }
+
+    #[test]
+    fn test_region_outlines() {
+        #[rustfmt::skip]
+        let grid = Grid::parse_char(&[
+            "AA.",
+            "A..",
+        ]);
+
+        let region = grid.connected_components(|ch| *ch == 'A').remove(0);
+        let p = Point::new;
+
+        assert_eq!(
+            region.outlines(),
+            [vec![
+                (p(0, 0), GridDirection::Up),
+                (p(1, 0), GridDirection::Up),
+                (p(1, 0), GridDirection::Right),
+                (p(1, 0), GridDirection::Down),
+                (p(0, 1), GridDirection::Right),
+                (p(0, 1), GridDirection::Down),
+                (p(0, 1), GridDirection::Left),
+                (p(0, 0), GridDirection::Left),
+            ]]
+        );
+    }
+
+    #[test]
+    fn test_region_sides_with_holes() {
+        #[rustfmt::skip]
+        let grid = Grid::parse_char(&[
+            "AAAAAA",
+            "AAABBA",
+            "AAABBA",
+            "ABBAAA",
+            "ABBAAA",
+            "AAAAAA",
+        ]);
+
+        let regions = grid.connected_components(|ch| *ch == 'A');
+
+        assert_eq!(regions.len(), 1);
+        assert_eq!(regions[0].area(), 28);
+        assert_eq!(regions[0].sides(), 12);
+        assert_eq!(regions[0].perimeter(), 40);
+
+        // the outside and both holes, each turning once per side
+        let outlines = regions[0].outlines();
+        assert_eq!(
+            outlines.iter().map(|o| o.len()).collect::<Vec<_>>(),
+            [24, 8, 8]
+        );
+        let turns = outlines
+            .iter()
+            .map(|outline| {
+                outline
+                    .iter()
+                    .zip(outline.iter().cycle().skip(1))
+                    .filter(|((_, a), (_, b))| a != b)
+                    .count()
+            })
+            .sum::<usize>();
+        assert_eq!(turns, regions[0].sides());
+
+        let regions = grid.connected_components(|ch| *ch == 'B');
+        assert_eq!(regions.len(), 2);
+    }
+
+    #[test]
+    fn test_try_parse() {
+        let grid = Grid::try_parse(&["12", "34"], |ch| ch.to_digit(10).ok_or("not a digit"));
+        assert_eq!(grid.unwrap(), Grid::from_vec(vec![1, 2, 3, 4], 2, 2));
+
+        let grid = Grid::try_parse(&["12", "3x"], |ch| ch.to_digit(10).ok_or("not a digit"));
+        assert_eq!(
+            grid.unwrap_err().to_string(),
+            "Grid::parse: could not parse `x` at x: 1, y: 1: not a digit"
+        );
+
+        assert!(Grid::try_parse_u32(&["12", "3x"]).is_err());
+    }
+
+    #[test]
+    fn test_try_parse_ragged() {
+        #[rustfmt::skip]
+        let lines = [
+            "#..",
+            "#",
+            "#.#.",
+        ];
+
+        let grid = Grid::try_parse(&lines, Ok::<char, String>);
+        assert_eq!(
+            grid.unwrap_err().to_string(),
+            "Grid::parse: row 0 has width 3, expected 4"
+        );
+
+        let grid = Grid::try_parse_padded(&lines, ' ', Ok::<char, String>).unwrap();
+        assert_eq!(grid.width, 4);
+        assert_eq!(grid.height, 3);
+        assert_eq!(grid.row(0), &['#', '.', '.', ' ']);
+        assert_eq!(grid.row(1), &['#', ' ', ' ', ' ']);
+        assert_eq!(grid.row(2), &['#', '.', '#', '.']);
+    }
+
+    #[test]
+    fn test_parse_closure() {
+        let offset = 10;
+        let grid = Grid::parse(&["12", "34"], |ch| ch.to_digit(10).unwrap() + offset);
+
+        assert_eq!(grid, Grid::from_vec(vec![11, 12, 13, 14], 2, 2));
+    }
+
+    #[test]
+    fn test_extract_markers() {
+        #[rustfmt::skip]
+        let mut grid = Grid::parse_char(&[
+            "S.#",
+            ".^.",
+            "#^E",
+        ]);
+
+        let markers = grid.extract_markers(&['S', 'E', '^'], '.');
+
+        assert_eq!(markers[&'S'], [Point::new(0, 0)]);
+        assert_eq!(markers[&'E'], [Point::new(2, 2)]);
+        assert_eq!(markers[&'^'], [Point::new(1, 1), Point::new(1, 2)]);
+        assert_eq!(grid, Grid::parse_char(&["..#", "...", "#.."]));
+    }
 }
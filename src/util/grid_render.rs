@@ -0,0 +1,301 @@
+use std::collections::HashMap;
+use std::fmt::{self, Display, Write};
+
+use colored::Colorize;
+
+pub use colored::Color;
+
+use super::grid::{Grid, Point};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct Style {
+    pub glyph: Option<char>,
+    pub fg: Option<Color>,
+    pub bg: Option<Color>,
+    pub bold: bool,
+}
+
+#[allow(dead_code)]
+impl Style {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn glyph(mut self, glyph: char) -> Self {
+        self.glyph = Some(glyph);
+        self
+    }
+
+    pub fn fg(mut self, color: Color) -> Self {
+        self.fg = Some(color);
+        self
+    }
+
+    pub fn bg(mut self, color: Color) -> Self {
+        self.bg = Some(color);
+        self
+    }
+
+    pub fn bold(mut self) -> Self {
+        self.bold = true;
+        self
+    }
+
+    // Fields set on `other` win, so later overlays are drawn on top of earlier ones.
+    fn layer(self, other: &Style) -> Self {
+        Self {
+            glyph: other.glyph.or(self.glyph),
+            fg: other.fg.or(self.fg),
+            bg: other.bg.or(self.bg),
+            bold: self.bold || other.bold,
+        }
+    }
+
+    fn is_coloured(&self) -> bool {
+        self.fg.is_some() || self.bg.is_some() || self.bold
+    }
+}
+
+// Renders a grid with overlays stacked on top of it. Output is plain text unless colour is
+// enabled, and every row ends with a newline so the result can be compared in tests.
+pub struct GridRenderer<'a, T> {
+    grid: &'a Grid<T>,
+    overlays: Vec<HashMap<Point, Style>>,
+    colour: bool,
+    axis_labels: bool,
+    viewport: Option<(Point, usize, usize)>,
+}
+
+#[allow(dead_code)]
+impl<'a, T> GridRenderer<'a, T>
+where
+    T: std::fmt::Display + std::fmt::Debug + Clone + PartialEq,
+{
+    pub fn new(grid: &'a Grid<T>) -> Self {
+        Self {
+            grid,
+            overlays: vec![],
+            colour: false,
+            axis_labels: false,
+            viewport: None,
+        }
+    }
+
+    pub fn colour(mut self, colour: bool) -> Self {
+        self.colour = colour;
+        self
+    }
+
+    // Adds column numbers above and row numbers beside the grid. Column numbers are written
+    // vertically, one digit per line, so they only line up for single character cells.
+    pub fn axis_labels(mut self, axis_labels: bool) -> Self {
+        self.axis_labels = axis_labels;
+        self
+    }
+
+    // Only render the `width` x `height` window starting at `origin`, clipped to the grid.
+    pub fn crop(mut self, origin: Point, width: usize, height: usize) -> Self {
+        self.viewport = Some((origin, width, height));
+        self
+    }
+
+    pub fn path(self, path: &[Point], style: Style) -> Self {
+        self.points(path.iter().copied(), style)
+    }
+
+    pub fn points<I: IntoIterator<Item = Point>>(self, points: I, style: Style) -> Self {
+        self.styles(points.into_iter().map(|point| (point, style)))
+    }
+
+    pub fn styles<I: IntoIterator<Item = (Point, Style)>>(mut self, styles: I) -> Self {
+        self.overlays.push(styles.into_iter().collect());
+        self
+    }
+
+    pub fn render_to<W: Write>(&self, out: &mut W) -> fmt::Result {
+        let (x_range, y_range) = self.bounds();
+
+        let gutter = if self.axis_labels {
+            y_range.end.saturating_sub(1).to_string().len()
+        } else {
+            0
+        };
+
+        if self.axis_labels && !x_range.is_empty() {
+            let digits = (x_range.end - 1).to_string().len();
+
+            for digit in 0..digits {
+                write!(out, "{:gutter$} ", "")?;
+
+                for x in x_range.clone() {
+                    let label = format!("{x:>digits$}");
+                    out.write_char(label.as_bytes()[digit] as char)?;
+                }
+
+                writeln!(out)?;
+            }
+        }
+
+        for y in y_range {
+            if self.axis_labels {
+                write!(out, "{y:>gutter$} ")?;
+            }
+
+            for x in x_range.clone() {
+                let point = Point::new(x, y);
+                let style = self
+                    .overlays
+                    .iter()
+                    .filter_map(|overlay| overlay.get(&point))
+                    .fold(Style::default(), |acc, style| acc.layer(style));
+
+                let cell = match style.glyph {
+                    Some(glyph) => glyph.to_string(),
+                    None => self.grid[&point].to_string(),
+                };
+
+                if self.colour && style.is_coloured() {
+                    let mut cell = cell.normal();
+                    if let Some(fg) = style.fg {
+                        cell = cell.color(fg);
+                    }
+                    if let Some(bg) = style.bg {
+                        cell = cell.on_color(bg);
+                    }
+                    if style.bold {
+                        cell = cell.bold();
+                    }
+
+                    write!(out, "{cell}")?;
+                } else {
+                    out.write_str(&cell)?;
+                }
+            }
+
+            writeln!(out)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn print(&self) {
+        print!("{self}");
+    }
+
+    fn bounds(&self) -> (std::ops::Range<usize>, std::ops::Range<usize>) {
+        let (origin, width, height) =
+            self.viewport
+                .unwrap_or((Point::new(0, 0), self.grid.width, self.grid.height));
+
+        let x_start = origin.x.min(self.grid.width);
+        let y_start = origin.y.min(self.grid.height);
+        let x_end = origin.x.saturating_add(width).min(self.grid.width);
+        let y_end = origin.y.saturating_add(height).min(self.grid.height);
+
+        (x_start..x_end, y_start..y_end)
+    }
+}
+
+impl<T> Display for GridRenderer<'_, T>
+where
+    T: std::fmt::Display + std::fmt::Debug + Clone + PartialEq,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.render_to(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn test_grid() -> Grid<char> {
+        #[rustfmt::skip]
+        let grid = Grid::parse_char(&[
+            "#.........",
+            "#.#####...",
+            "#.....#...",
+            "#######..E",
+        ]);
+
+        grid
+    }
+
+    #[test]
+    fn test_render_plain() {
+        let grid = test_grid();
+
+        let rendered = GridRenderer::new(&grid).to_string();
+
+        assert_eq!(rendered, "#.........\n#.#####...\n#.....#...\n#######..E\n");
+        assert_eq!(grid.to_string(), rendered);
+    }
+
+    #[test]
+    fn test_render_overlays() {
+        let grid = test_grid();
+        let path = [
+            Point::new(1, 0),
+            Point::new(1, 1),
+            Point::new(1, 2),
+            Point::new(2, 2),
+        ];
+
+        let rendered = GridRenderer::new(&grid)
+            .path(&path, Style::new().glyph('O').fg(Color::Blue))
+            .points([Point::new(1, 0)], Style::new().glyph('S'))
+            .styles([(Point::new(9, 3), Style::new().bold())])
+            .to_string();
+
+        assert_eq!(rendered, "#S........\n#O#####...\n#OO...#...\n#######..E\n");
+    }
+
+    #[test]
+    fn test_render_axis_labels_and_crop() {
+        let grid = test_grid();
+
+        let rendered = GridRenderer::new(&grid).axis_labels(true).to_string();
+
+        #[rustfmt::skip]
+        let expected = [
+            "  0123456789",
+            "0 #.........",
+            "1 #.#####...",
+            "2 #.....#...",
+            "3 #######..E",
+            "",
+        ];
+        assert_eq!(rendered, expected.join("\n"));
+
+        let rendered = GridRenderer::new(&grid)
+            .axis_labels(true)
+            .crop(Point::new(5, 2), 10, 10)
+            .to_string();
+
+        #[rustfmt::skip]
+        let expected = [
+            "  56789",
+            "2 .#...",
+            "3 ##..E",
+            "",
+        ];
+        assert_eq!(rendered, expected.join("\n"));
+    }
+
+    #[test]
+    fn test_render_wide_axis_labels() {
+        let grid = Grid::new(12, 1, '.');
+
+        let rendered = GridRenderer::new(&grid).axis_labels(true).to_string();
+
+        #[rustfmt::skip]
+        let expected = [
+            "            11",
+            "  012345678901",
+            "0 ............",
+            "",
+        ];
+        assert_eq!(rendered, expected.join("\n"));
+    }
+}
@@ -0,0 +1,295 @@
+use anyhow::{Result, anyhow};
+use std::ops::{Add, Mul, Neg, Sub};
+use std::str::FromStr;
+
+use super::point::Point3;
+
+// A cell of a hex grid in axial coordinates. The third cube coordinate is implied, the three
+// always add up to zero, so `q + r + s == 0`.
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone, Default)]
+pub struct Hex {
+    pub q: i64,
+    pub r: i64,
+}
+
+#[allow(dead_code)]
+impl Hex {
+    pub fn new(q: i64, r: i64) -> Self {
+        Self { q, r }
+    }
+
+    pub fn s(&self) -> i64 {
+        -self.q - self.r
+    }
+
+    pub fn from_cube(cube: Point3) -> Option<Self> {
+        (cube.x + cube.y + cube.z == 0).then(|| Self::new(cube.x, cube.y))
+    }
+
+    pub fn to_cube(self) -> Point3 {
+        Point3::new(self.q, self.r, self.s())
+    }
+
+    pub fn neighbour(self, direction: HexDirection) -> Self {
+        self + direction.delta()
+    }
+
+    pub fn neighbours(self) -> impl Iterator<Item = Self> {
+        HexDirection::all()
+            .into_iter()
+            .map(move |direction| self.neighbour(direction))
+    }
+
+    // The fewest steps between two cells.
+    pub fn distance(&self, other: Self) -> i64 {
+        (*self - other)
+            .to_cube()
+            .chebyshev_distance(Point3::default())
+    }
+
+    // The cells exactly `radius` steps away, going counter clockwise from the East.
+    pub fn ring(self, radius: i64) -> Vec<Self> {
+        if radius == 0 {
+            return vec![self];
+        }
+
+        let mut cells = vec![];
+        let mut cell = self + HexDirection::East.delta() * radius;
+
+        for direction in HexDirection::all() {
+            let side = direction
+                .turn_counter_clockwise_60()
+                .turn_counter_clockwise_60();
+            for _ in 0..radius {
+                cells.push(cell);
+                cell = cell.neighbour(side);
+            }
+        }
+
+        cells
+    }
+
+    // Follows a run of directions written without separators, like `nwwswee`.
+    pub fn walk(self, path: &str) -> Result<Self> {
+        Ok(HexDirection::parse_path(path)?
+            .into_iter()
+            .fold(self, Self::neighbour))
+    }
+}
+
+impl Add for Hex {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.q + other.q, self.r + other.r)
+    }
+}
+
+impl Sub for Hex {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.q - other.q, self.r - other.r)
+    }
+}
+
+impl Mul<i64> for Hex {
+    type Output = Self;
+
+    fn mul(self, by: i64) -> Self {
+        Self::new(self.q * by, self.r * by)
+    }
+}
+
+impl Neg for Hex {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.q, -self.r)
+    }
+}
+
+// The six ways out of a pointy topped hex, rows run East to West. For a flat topped grid
+// turn the picture 90 degrees, North becomes East and so on.
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
+pub enum HexDirection {
+    East,
+    NorthEast,
+    NorthWest,
+    West,
+    SouthWest,
+    SouthEast,
+}
+
+#[allow(dead_code)]
+impl HexDirection {
+    // Counter clockwise from East.
+    pub fn all() -> [Self; 6] {
+        [
+            Self::East,
+            Self::NorthEast,
+            Self::NorthWest,
+            Self::West,
+            Self::SouthWest,
+            Self::SouthEast,
+        ]
+    }
+
+    pub fn turn_clockwise_60(self) -> Self {
+        match self {
+            Self::East => Self::SouthEast,
+            Self::SouthEast => Self::SouthWest,
+            Self::SouthWest => Self::West,
+            Self::West => Self::NorthWest,
+            Self::NorthWest => Self::NorthEast,
+            Self::NorthEast => Self::East,
+        }
+    }
+
+    pub fn turn_counter_clockwise_60(self) -> Self {
+        match self {
+            Self::East => Self::NorthEast,
+            Self::NorthEast => Self::NorthWest,
+            Self::NorthWest => Self::West,
+            Self::West => Self::SouthWest,
+            Self::SouthWest => Self::SouthEast,
+            Self::SouthEast => Self::East,
+        }
+    }
+
+    pub fn opposite(self) -> Self {
+        match self {
+            Self::East => Self::West,
+            Self::West => Self::East,
+            Self::NorthEast => Self::SouthWest,
+            Self::SouthWest => Self::NorthEast,
+            Self::NorthWest => Self::SouthEast,
+            Self::SouthEast => Self::NorthWest,
+        }
+    }
+
+    // r grows to the South, as y does on a square grid.
+    pub fn delta(self) -> Hex {
+        let (q, r) = match self {
+            Self::East => (1, 0),
+            Self::NorthEast => (1, -1),
+            Self::NorthWest => (0, -1),
+            Self::West => (-1, 0),
+            Self::SouthWest => (-1, 1),
+            Self::SouthEast => (0, 1),
+        };
+
+        Hex::new(q, r)
+    }
+
+    // Directions written back to back, like `esenee`, the two letter ones never clash.
+    pub fn parse_path(path: &str) -> Result<Vec<Self>> {
+        let mut directions = vec![];
+        let mut rest = path.trim();
+
+        while !rest.is_empty() {
+            let length = if rest.starts_with(['n', 's']) { 2 } else { 1 };
+            let token = rest
+                .get(..length)
+                .ok_or_else(|| anyhow!("HexDirection::parse_path: `{rest}` is cut short"))?;
+
+            directions.push(token.parse()?);
+            rest = &rest[length..];
+        }
+
+        Ok(directions)
+    }
+}
+
+impl FromStr for HexDirection {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "e" | "E" => Ok(Self::East),
+            "ne" | "NE" => Ok(Self::NorthEast),
+            "nw" | "NW" => Ok(Self::NorthWest),
+            "w" | "W" => Ok(Self::West),
+            "sw" | "SW" => Ok(Self::SouthWest),
+            "se" | "SE" => Ok(Self::SouthEast),
+            _ => Err(anyhow!("Could not parse `{s}` as HexDirection")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_hex_neighbours() {
+        let origin = Hex::default();
+
+        let neighbours = origin.neighbours().collect::<HashSet<_>>();
+        assert_eq!(neighbours.len(), 6);
+        assert!(neighbours.iter().all(|hex| hex.distance(origin) == 1));
+        assert!(neighbours.iter().all(|hex| hex.q + hex.r + hex.s() == 0));
+
+        for direction in HexDirection::all() {
+            assert_eq!(direction.opposite().delta(), -direction.delta());
+            assert_eq!(
+                direction.turn_clockwise_60().turn_counter_clockwise_60(),
+                direction
+            );
+            assert_eq!(
+                origin.neighbour(direction).neighbour(direction.opposite()),
+                origin
+            );
+        }
+    }
+
+    #[test]
+    fn test_hex_distance() {
+        let cases = [
+            (Hex::new(0, 0), Hex::new(3, 0), 3),
+            (Hex::new(0, 0), Hex::new(2, -3), 3),
+            (Hex::new(-2, 1), Hex::new(2, -1), 4),
+            (Hex::new(1, 1), Hex::new(-1, -1), 4),
+            (Hex::new(5, -5), Hex::new(5, -5), 0),
+        ];
+
+        for (a, b, expected) in cases {
+            assert_eq!(a.distance(b), expected, "{a:?} to {b:?}");
+            assert_eq!(b.distance(a), expected, "{b:?} to {a:?}");
+        }
+
+        let cube = Hex::new(2, -5).to_cube();
+        assert_eq!(Hex::from_cube(cube), Some(Hex::new(2, -5)));
+        assert_eq!(Hex::from_cube(Point3::new(1, 1, 1)), None);
+    }
+
+    #[test]
+    fn test_hex_ring() {
+        let centre = Hex::new(1, -2);
+
+        assert_eq!(centre.ring(0), [centre]);
+        for radius in 1..4 {
+            let ring = centre.ring(radius);
+
+            assert_eq!(ring.len(), 6 * radius as usize);
+            assert_eq!(ring.iter().collect::<HashSet<_>>().len(), ring.len());
+            assert!(ring.iter().all(|hex| hex.distance(centre) == radius));
+        }
+    }
+
+    #[test]
+    fn test_hex_walk() {
+        let origin = Hex::default();
+
+        assert_eq!(
+            origin.walk("esew").unwrap(),
+            origin.neighbour(HexDirection::SouthEast)
+        );
+        assert_eq!(origin.walk("nwwswee").unwrap(), origin);
+        assert_eq!(origin.walk("").unwrap(), origin);
+        assert!(origin.walk("ex").is_err());
+        assert!(origin.walk("en").is_err());
+    }
+}
@@ -0,0 +1,483 @@
+use super::rational::Rational;
+
+// Small integer linear programs: minimise `objective · x` subject to equality constraints,
+// x >= 0 and optional upper bounds, with every x an integer. Solved by branch and bound
+// over the linear relaxation, which is solved exactly with a rational simplex so there is
+// no floating point tolerance to tune. Meant for the handful of variables a puzzle has.
+//
+// Branching only ends by itself when every variable has an upper bound, given or implied by
+// the constraints. Otherwise a system like 2x - 2y = 1, which has rational solutions but no
+// integer ones, could be branched on forever, so the search gives up past a depth limit.
+
+// How deep to branch when some variable has no upper bound.
+const UNBOUNDED_DEPTH: usize = 64;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct IntegerProgram {
+    variables: usize,
+    objective: Vec<i64>,
+    constraints: Vec<(Vec<i64>, i64)>,
+    upper_bounds: Vec<Option<i64>>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct IntegerSolution {
+    pub values: Vec<i64>,
+    pub objective: i64,
+}
+
+#[allow(dead_code)]
+impl IntegerProgram {
+    pub fn new(variables: usize) -> Self {
+        Self {
+            variables,
+            objective: vec![0; variables],
+            constraints: vec![],
+            upper_bounds: vec![None; variables],
+        }
+    }
+
+    pub fn minimise(mut self, objective: &[i64]) -> Self {
+        assert_eq!(objective.len(), self.variables, "objective length mismatch");
+        self.objective = objective.to_vec();
+
+        self
+    }
+
+    pub fn maximise(self, objective: &[i64]) -> Self {
+        let negated = objective.iter().map(|c| -c).collect::<Vec<_>>();
+
+        self.minimise(&negated)
+    }
+
+    // Adds the constraint `coefficients · x = rhs`.
+    pub fn equal(mut self, coefficients: &[i64], rhs: i64) -> Self {
+        assert_eq!(
+            coefficients.len(),
+            self.variables,
+            "constraint length mismatch"
+        );
+        self.constraints.push((coefficients.to_vec(), rhs));
+
+        self
+    }
+
+    pub fn upper_bound(mut self, variable: usize, bound: i64) -> Self {
+        self.upper_bounds[variable] = Some(bound);
+
+        self
+    }
+
+    // The optimal integer solution, None if there is no integer solution at all or, with a
+    // variable that can't be bounded, none was found within `UNBOUNDED_DEPTH` branches.
+    // Panics if the objective is unbounded, which a well posed puzzle never is.
+    pub fn solve(&self) -> Option<IntegerSolution> {
+        let lower = vec![0; self.variables];
+        let upper = self.implied_bounds()?;
+        let depth = match upper.iter().all(Option::is_some) {
+            true => usize::MAX,
+            false => UNBOUNDED_DEPTH,
+        };
+        let mut best = None;
+
+        self.branch(lower, upper, depth, &mut best);
+
+        best
+    }
+
+    // The upper bounds tightened by the constraints. With every x >= 0 a variable can be no
+    // more than what a row leaves for it once the other terms are as small as they can be,
+    // and each bound found can tighten others. None if a bound drops below zero.
+    fn implied_bounds(&self) -> Option<Vec<Option<i64>>> {
+        let mut upper = self.upper_bounds.clone();
+
+        loop {
+            let mut changed = false;
+
+            for (coefficients, rhs) in self.constraints.iter() {
+                for (variable, coefficient) in coefficients.iter().enumerate() {
+                    if *coefficient == 0 {
+                        continue;
+                    }
+
+                    // |a|x = sign * rhs - sum(sign * c * y) over the other variables y
+                    let sign = coefficient.signum() as i128;
+                    let others = coefficients
+                        .iter()
+                        .enumerate()
+                        .filter(|(other, _)| *other != variable)
+                        .map(|(other, c)| match -sign * *c as i128 {
+                            ..=0 => Some(0),
+                            c => upper[other].map(|bound| c * bound as i128),
+                        })
+                        .sum::<Option<i128>>();
+                    let Some(others) = others else {
+                        continue;
+                    };
+
+                    let bound =
+                        (sign * *rhs as i128 + others).div_euclid(coefficient.abs() as i128);
+                    let Ok(bound) = i64::try_from(bound) else {
+                        continue;
+                    };
+
+                    if upper[variable].is_none_or(|current| bound < current) {
+                        if bound < 0 {
+                            return None;
+                        }
+                        upper[variable] = Some(bound);
+                        changed = true;
+                    }
+                }
+            }
+
+            if !changed {
+                return Some(upper);
+            }
+        }
+    }
+
+    // Depth first, each node narrows the bounds of one fractional variable.
+    fn branch(
+        &self,
+        lower: Vec<i64>,
+        upper: Vec<Option<i64>>,
+        depth: usize,
+        best: &mut Option<IntegerSolution>,
+    ) {
+        if depth == 0 {
+            return;
+        }
+
+        let Some((values, objective)) = self.relaxation(&lower, &upper) else {
+            return;
+        };
+
+        // the objective is integral on integer points, so a relaxation that can't beat the
+        // best so far even after rounding up can't lead anywhere better
+        if let Some(best) = best
+            && objective.ceil() as i64 >= best.objective
+        {
+            return;
+        }
+
+        let Some((variable, value)) = values
+            .iter()
+            .enumerate()
+            .find(|(_, value)| !value.is_integer())
+        else {
+            *best = Some(IntegerSolution {
+                values: values.iter().map(|value| value.numer() as i64).collect(),
+                objective: objective.numer() as i64,
+            });
+            return;
+        };
+
+        let mut down = upper.clone();
+        down[variable] = Some(value.floor() as i64);
+        self.branch(lower.clone(), down, depth - 1, best);
+
+        let mut up = lower;
+        up[variable] = value.ceil() as i64;
+        self.branch(up, upper, depth - 1, best);
+    }
+
+    // Solves the linear relaxation with `lower <= x <= upper`. Shifting by the lower bounds
+    // keeps every variable non-negative and each upper bound becomes a row with a slack.
+    fn relaxation(
+        &self,
+        lower: &[i64],
+        upper: &[Option<i64>],
+    ) -> Option<(Vec<Rational>, Rational)> {
+        let bounded = (0..self.variables)
+            .filter_map(|variable| upper[variable].map(|bound| (variable, bound)))
+            .collect::<Vec<_>>();
+
+        if bounded
+            .iter()
+            .any(|(variable, bound)| *bound < lower[*variable])
+        {
+            return None;
+        }
+
+        let columns = self.variables + bounded.len();
+        let mut rows = vec![];
+        let mut rhs = vec![];
+
+        for (coefficients, value) in self.constraints.iter() {
+            let mut row = vec![Rational::zero(); columns];
+            let mut shifted = *value as i128;
+
+            for (variable, coefficient) in coefficients.iter().enumerate() {
+                row[variable] = Rational::from(*coefficient as i128);
+                shifted -= *coefficient as i128 * lower[variable] as i128;
+            }
+
+            rows.push(row);
+            rhs.push(Rational::from(shifted));
+        }
+
+        for (slack, (variable, bound)) in bounded.iter().enumerate() {
+            let mut row = vec![Rational::zero(); columns];
+            row[*variable] = Rational::one();
+            row[self.variables + slack] = Rational::one();
+
+            rows.push(row);
+            rhs.push(Rational::from((*bound - lower[*variable]) as i128));
+        }
+
+        let mut costs = vec![Rational::zero(); columns];
+        for (variable, cost) in self.objective.iter().enumerate() {
+            costs[variable] = Rational::from(*cost as i128);
+        }
+
+        let shifted = Simplex::minimise(rows, rhs, &costs)?;
+
+        let values = (0..self.variables)
+            .map(|variable| shifted[variable] + Rational::from(lower[variable] as i128))
+            .collect::<Vec<_>>();
+        let objective = values
+            .iter()
+            .zip(self.objective.iter())
+            .fold(Rational::zero(), |acc, (value, cost)| {
+                acc + *value * Rational::from(*cost as i128)
+            });
+
+        Some((values, objective))
+    }
+}
+
+// A dense tableau for `rows · x = rhs`, x >= 0, with one artificial variable per row.
+struct Simplex {
+    tableau: Vec<Vec<Rational>>,
+    basis: Vec<usize>,
+}
+
+impl Simplex {
+    // Two phase simplex with Bland's rule, so it can't cycle. Returns the values of the
+    // original columns at an optimum, None if infeasible.
+    fn minimise(
+        rows: Vec<Vec<Rational>>,
+        rhs: Vec<Rational>,
+        costs: &[Rational],
+    ) -> Option<Vec<Rational>> {
+        let columns = costs.len();
+        let row_count = rows.len();
+
+        let tableau = rows
+            .into_iter()
+            .zip(rhs)
+            .enumerate()
+            .map(|(i, (mut row, value))| {
+                // artificial variables need a non-negative right hand side to start feasible
+                if value < Rational::zero() {
+                    row.iter_mut().for_each(|c| *c = -*c);
+                }
+
+                row.extend((0..row_count).map(|j| {
+                    if i == j {
+                        Rational::one()
+                    } else {
+                        Rational::zero()
+                    }
+                }));
+                row.push(value.abs());
+
+                row
+            })
+            .collect::<Vec<_>>();
+
+        let mut simplex = Self {
+            tableau,
+            basis: (columns..columns + row_count).collect(),
+        };
+
+        let mut phase_one = vec![Rational::zero(); columns];
+        phase_one.extend(vec![Rational::one(); row_count]);
+        simplex.optimise(&phase_one, columns + row_count);
+
+        if simplex.value(&phase_one) != Rational::zero() {
+            return None;
+        }
+
+        // pivot any artificial still in the basis out, a row with nothing to pivot on is
+        // redundant and stays at zero
+        for row in 0..row_count {
+            if simplex.basis[row] >= columns
+                && let Some(column) = (0..columns).find(|c| !simplex.tableau[row][*c].is_zero())
+            {
+                simplex.pivot(row, column);
+            }
+        }
+
+        let mut phase_two = costs.to_vec();
+        phase_two.extend(vec![Rational::zero(); row_count]);
+        assert!(
+            simplex.optimise(&phase_two, columns),
+            "integer program is unbounded"
+        );
+
+        let mut values = vec![Rational::zero(); columns];
+        for (row, column) in simplex.basis.iter().enumerate() {
+            if *column < columns {
+                values[*column] = *simplex.tableau[row].last().unwrap();
+            }
+        }
+
+        Some(values)
+    }
+
+    fn value(&self, costs: &[Rational]) -> Rational {
+        self.basis
+            .iter()
+            .zip(self.tableau.iter())
+            .fold(Rational::zero(), |acc, (column, row)| {
+                acc + costs[*column] * *row.last().unwrap()
+            })
+    }
+
+    // Pivots until no column below `enterable` improves the objective. False if unbounded.
+    fn optimise(&mut self, costs: &[Rational], enterable: usize) -> bool {
+        loop {
+            let entering = (0..enterable).find(|column| {
+                let reduced = self
+                    .basis
+                    .iter()
+                    .zip(self.tableau.iter())
+                    .fold(costs[*column], |acc, (basic, row)| {
+                        acc - costs[*basic] * row[*column]
+                    });
+
+                reduced < Rational::zero()
+            });
+
+            let Some(entering) = entering else {
+                return true;
+            };
+
+            let leaving = (0..self.tableau.len())
+                .filter(|row| self.tableau[*row][entering] > Rational::zero())
+                .min_by_key(|row| {
+                    let ratio = *self.tableau[*row].last().unwrap() / self.tableau[*row][entering];
+                    (ratio, self.basis[*row])
+                });
+
+            let Some(leaving) = leaving else {
+                return false;
+            };
+
+            self.pivot(leaving, entering);
+        }
+    }
+
+    fn pivot(&mut self, row: usize, column: usize) {
+        let pivot = self.tableau[row][column];
+        self.tableau[row].iter_mut().for_each(|c| *c /= pivot);
+
+        let pivot_row = self.tableau[row].clone();
+        for (i, other) in self.tableau.iter_mut().enumerate() {
+            let factor = other[column];
+            if i == row || factor.is_zero() {
+                continue;
+            }
+
+            for (c, value) in other.iter_mut().zip(pivot_row.iter()) {
+                *c -= factor * *value;
+            }
+        }
+
+        self.basis[row] = column;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_fewest_presses() {
+        // 2025 day 10, buttons (3) (1,3) (2) (2,3) (0,2) (0,1) with joltage {3,5,4,7}
+        #[rustfmt::skip]
+        let program = IntegerProgram::new(6)
+            .minimise(&[1, 1, 1, 1, 1, 1])
+            .equal(&[0, 0, 0, 0, 1, 1], 3)
+            .equal(&[0, 1, 0, 0, 0, 1], 5)
+            .equal(&[0, 0, 1, 1, 1, 0], 4)
+            .equal(&[1, 1, 0, 1, 0, 0], 7);
+
+        let solution = program.solve().unwrap();
+        assert_eq!(solution.objective, 10);
+        assert_eq!(solution.values.iter().sum::<i64>(), 10);
+    }
+
+    #[test]
+    fn test_relaxation_is_fractional() {
+        // maximise y with -x + y <= 1, 3x + 2y <= 12 and 2x + 3y <= 12, using slacks. The
+        // relaxation peaks at (1.8, 2.8) but no integer point gets above y = 2
+        let rows = [[-1, 1, 1, 0, 0], [3, 2, 0, 1, 0], [2, 3, 0, 0, 1]];
+        let rhs = [1, 12, 12];
+
+        let program = rows
+            .iter()
+            .zip(rhs)
+            .fold(IntegerProgram::new(5), |program, (row, rhs)| {
+                program.equal(row, rhs)
+            })
+            .maximise(&[0, 1, 0, 0, 0]);
+
+        let solution = program.solve().unwrap();
+        assert_eq!(solution.objective, -2);
+        assert_eq!(solution.values[1], 2);
+
+        for (row, rhs) in rows.iter().zip(rhs) {
+            let lhs = row
+                .iter()
+                .zip(solution.values.iter())
+                .map(|(a, x)| a * x)
+                .sum::<i64>();
+            assert_eq!(lhs, rhs);
+        }
+    }
+
+    #[test]
+    fn test_infeasible() {
+        // 2x = 3 has no integer solution
+        let program = IntegerProgram::new(1).minimise(&[1]).equal(&[2], 3);
+        assert_eq!(program.solve(), None);
+
+        let program = IntegerProgram::new(2)
+            .minimise(&[1, 1])
+            .equal(&[1, 1], 5)
+            .upper_bound(0, 1)
+            .upper_bound(1, 1);
+        assert_eq!(program.solve(), None);
+    }
+
+    #[test]
+    fn test_infeasible_unbounded() {
+        // 2x - 2y = 1 has rational solutions for every x, but no integer ones
+        let program = IntegerProgram::new(2).minimise(&[1, 1]).equal(&[2, -2], 1);
+        assert_eq!(program.solve(), None);
+
+        // x - y = 0 can't bound either variable, but is still solved
+        let program = IntegerProgram::new(2).minimise(&[1, 1]).equal(&[1, -1], 0);
+        assert_eq!(program.solve().unwrap().values, [0, 0]);
+    }
+
+    #[test]
+    fn test_implied_bounds() {
+        // x + 2y = 7 bounds both, then z - x - y = 1 bounds z by what x and y can be
+        let program = IntegerProgram::new(3)
+            .equal(&[1, 2, 0], 7)
+            .equal(&[-1, -1, 1], 1)
+            .upper_bound(0, 5);
+        assert_eq!(
+            program.implied_bounds(),
+            Some(vec![Some(5), Some(3), Some(9)])
+        );
+
+        let program = IntegerProgram::new(2).equal(&[1, 1], -1);
+        assert_eq!(program.implied_bounds(), None);
+    }
+}
@@ -0,0 +1,300 @@
+use std::ops::{Add, Range, Sub};
+
+// A set of values stored as sorted, disjoint, non-adjacent half open ranges.
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Default)]
+pub struct RangeSet<T> {
+    ranges: Vec<Range<T>>,
+}
+
+#[allow(dead_code)]
+impl<T> RangeSet<T>
+where
+    T: Copy + Ord,
+{
+    pub fn new() -> Self {
+        Self { ranges: vec![] }
+    }
+
+    pub fn from_ranges<I: IntoIterator<Item = Range<T>>>(ranges: I) -> Self {
+        let mut ranges = ranges
+            .into_iter()
+            .filter(|r| r.start < r.end)
+            .collect::<Vec<_>>();
+
+        ranges.sort_by_key(|r| r.start);
+
+        let mut merged: Vec<Range<T>> = vec![];
+
+        for range in ranges {
+            if let Some(last) = merged.last_mut()
+                && range.start <= last.end
+            {
+                last.end = last.end.max(range.end);
+                continue;
+            }
+
+            merged.push(range);
+        }
+
+        Self { ranges: merged }
+    }
+
+    pub fn from_range(range: Range<T>) -> Self {
+        Self::from_ranges(std::iter::once(range))
+    }
+
+    pub fn ranges(&self) -> &[Range<T>] {
+        &self.ranges
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    pub fn min(&self) -> Option<T> {
+        self.ranges.first().map(|r| r.start)
+    }
+
+    pub fn max_exclusive(&self) -> Option<T> {
+        self.ranges.last().map(|r| r.end)
+    }
+
+    pub fn insert(&mut self, range: Range<T>) {
+        *self = self.union(&Self::from_range(range));
+    }
+
+    pub fn remove(&mut self, range: Range<T>) {
+        *self = self.difference(&Self::from_range(range));
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        Self::from_ranges(self.ranges.iter().chain(other.ranges.iter()).cloned())
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = vec![];
+
+        let (mut i, mut j) = (0, 0);
+        while i < self.ranges.len() && j < other.ranges.len() {
+            let a = &self.ranges[i];
+            let b = &other.ranges[j];
+
+            let start = a.start.max(b.start);
+            let end = a.end.min(b.end);
+            if start < end {
+                ranges.push(start..end);
+            }
+
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        Self { ranges }
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut ranges = vec![];
+
+        let mut j = 0;
+        for range in self.ranges.iter() {
+            let mut start = range.start;
+
+            while j < other.ranges.len() && other.ranges[j].end <= start {
+                j += 1;
+            }
+
+            let mut k = j;
+            while k < other.ranges.len() && other.ranges[k].start < range.end {
+                let cut = &other.ranges[k];
+                if cut.start > start {
+                    ranges.push(start..cut.start);
+                }
+                start = start.max(cut.end);
+                k += 1;
+            }
+
+            if start < range.end {
+                ranges.push(start..range.end);
+            }
+        }
+
+        Self { ranges }
+    }
+
+    // Splits into the values below `at` and the values at or above it.
+    pub fn split_at(&self, at: T) -> (Self, Self) {
+        let mut below = vec![];
+        let mut above = vec![];
+
+        for range in self.ranges.iter() {
+            if range.end <= at {
+                below.push(range.clone());
+            } else if range.start >= at {
+                above.push(range.clone());
+            } else {
+                below.push(range.start..at);
+                above.push(at..range.end);
+            }
+        }
+
+        (Self { ranges: below }, Self { ranges: above })
+    }
+
+    pub fn contains(&self, value: &T) -> bool {
+        let idx = self.ranges.partition_point(|r| r.end <= *value);
+
+        self.ranges
+            .get(idx)
+            .is_some_and(|r| r.start <= *value && *value < r.end)
+    }
+
+    pub fn contains_range(&self, range: &Range<T>) -> bool {
+        if range.start >= range.end {
+            return true;
+        }
+
+        let idx = self.ranges.partition_point(|r| r.end <= range.start);
+
+        self.ranges
+            .get(idx)
+            .is_some_and(|r| r.start <= range.start && range.end <= r.end)
+    }
+
+    pub fn overlaps(&self, range: &Range<T>) -> bool {
+        let idx = self.ranges.partition_point(|r| r.end <= range.start);
+
+        self.ranges
+            .get(idx)
+            .is_some_and(|r| r.start < range.end && range.start < range.end)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Range<T>> {
+        self.ranges.iter()
+    }
+}
+
+#[allow(dead_code)]
+impl<T> RangeSet<T>
+where
+    T: Copy + Ord + Default + Add<Output = T> + Sub<Output = T>,
+{
+    pub fn total_len(&self) -> T {
+        self.ranges
+            .iter()
+            .fold(T::default(), |acc, r| acc + (r.end - r.start))
+    }
+}
+
+#[allow(dead_code)]
+impl<T> RangeSet<T>
+where
+    T: Copy + Ord + Add<Output = T>,
+{
+    // Shifts every value covered by a source range by that range's offset. The
+    // first matching source range wins, and uncovered values map to themselves.
+    pub fn map_offsets<I>(&self, mappings: I) -> Self
+    where
+        I: IntoIterator<Item = (Range<T>, T)>,
+    {
+        let mut unmapped = self.clone();
+        let mut mapped = vec![];
+
+        for (source, offset) in mappings {
+            let source = Self::from_range(source);
+
+            for range in unmapped.intersection(&source).iter() {
+                mapped.push(range.start + offset..range.end + offset);
+            }
+
+            unmapped = unmapped.difference(&source);
+        }
+
+        Self::from_ranges(mapped.into_iter().chain(unmapped.ranges))
+    }
+}
+
+impl<T> FromIterator<Range<T>> for RangeSet<T>
+where
+    T: Copy + Ord,
+{
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        Self::from_ranges(iter)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::single_range_in_vec_init)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_from_ranges_merges() {
+        let set = RangeSet::from_ranges([10..15, 3..6, 16..21, 12..19, 6..7, 30..30]);
+
+        assert_eq!(set.ranges(), &[3..7, 10..21]);
+        assert_eq!(set.total_len(), 15);
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a = RangeSet::from_ranges([0..10, 20..30]);
+        let b = RangeSet::from_ranges([5..25, 28..40]);
+
+        assert_eq!(a.union(&b).ranges(), &[0..40]);
+        assert_eq!(a.intersection(&b).ranges(), &[5..10, 20..25, 28..30]);
+        assert_eq!(a.difference(&b).ranges(), &[0..5, 25..28]);
+        assert_eq!(b.difference(&a).ranges(), &[10..20, 30..40]);
+
+        let mut c = a.clone();
+        c.remove(2..4);
+        c.insert(9..21);
+        assert_eq!(c.ranges(), &[0..2, 4..30]);
+    }
+
+    #[test]
+    fn test_split_at() {
+        let set = RangeSet::from_ranges([0..10, 20..30]);
+
+        let (below, above) = set.split_at(25);
+        assert_eq!(below.ranges(), &[0..10, 20..25]);
+        assert_eq!(above.ranges(), &[25..30]);
+
+        let (below, above) = set.split_at(15);
+        assert_eq!(below.ranges(), &[0..10]);
+        assert_eq!(above.ranges(), &[20..30]);
+    }
+
+    #[test]
+    fn test_containment() {
+        let set = RangeSet::from_ranges([3..6, 10..21]);
+
+        for (value, expected) in [(2, false), (3, true), (5, true), (6, false), (20, true)] {
+            assert_eq!(set.contains(&value), expected, "contains({value})");
+        }
+
+        assert!(set.contains_range(&(10..21)));
+        assert!(!set.contains_range(&(5..11)));
+        assert!(set.overlaps(&(5..11)));
+        assert!(!set.overlaps(&(6..10)));
+    }
+
+    #[test]
+    fn test_map_offsets() {
+        // seed-to-soil map from 2023 day 5
+        let seeds = RangeSet::from_ranges([79..93, 55..68]);
+        let mappings = [(98..100, -48), (50..98, 2)];
+
+        let soil = seeds.map_offsets(mappings);
+
+        assert_eq!(soil.ranges(), &[57..70, 81..95]);
+
+        let set = RangeSet::from_range(0..10);
+        let mapped = set.map_offsets([(2..4, 100), (3..6, -1)]);
+
+        assert_eq!(mapped.ranges(), &[0..2, 3..5, 6..10, 102..104]);
+    }
+}
@@ -0,0 +1,526 @@
+use std::ops::{Add, Index, IndexMut, Mul};
+
+use super::rational::Rational;
+
+// A dense row-major matrix.
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct Matrix<T> {
+    data: Vec<T>,
+    pub rows: usize,
+    pub cols: usize,
+}
+
+#[allow(dead_code)]
+impl<T> Matrix<T>
+where
+    T: Copy + Default,
+{
+    pub fn new(rows: usize, cols: usize) -> Self {
+        Self {
+            data: vec![T::default(); rows * cols],
+            rows,
+            cols,
+        }
+    }
+
+    pub fn from_rows<R: AsRef<[T]>>(rows: &[R]) -> Self {
+        let cols = rows.first().map_or(0, |row| row.as_ref().len());
+        assert!(
+            rows.iter().all(|row| row.as_ref().len() == cols),
+            "Matrix::from_rows: rows have different lengths"
+        );
+
+        Self {
+            data: rows.iter().flat_map(|row| row.as_ref()).copied().collect(),
+            rows: rows.len(),
+            cols,
+        }
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.data[row * self.cols..(row + 1) * self.cols]
+    }
+
+    pub fn column(&self, col: usize) -> Vec<T> {
+        (0..self.rows).map(|row| self[(row, col)]).collect()
+    }
+
+    pub fn transpose(&self) -> Self {
+        let mut transposed = Self::new(self.cols, self.rows);
+
+        for row in 0..self.rows {
+            for col in 0..self.cols {
+                transposed[(col, row)] = self[(row, col)];
+            }
+        }
+
+        transposed
+    }
+
+    pub fn map<U, F: Fn(T) -> U>(&self, f: F) -> Matrix<U> {
+        Matrix {
+            data: self.data.iter().map(|value| f(*value)).collect(),
+            rows: self.rows,
+            cols: self.cols,
+        }
+    }
+
+    fn swap_rows(&mut self, a: usize, b: usize) {
+        for col in 0..self.cols {
+            self.data.swap(a * self.cols + col, b * self.cols + col);
+        }
+    }
+}
+
+#[allow(dead_code)]
+impl<T> Matrix<T>
+where
+    T: Copy + Default + Add<Output = T> + Mul<Output = T>,
+{
+    pub fn mul_vec(&self, vector: &[T]) -> Vec<T> {
+        assert_eq!(vector.len(), self.cols, "Matrix::mul_vec: length mismatch");
+
+        (0..self.rows)
+            .map(|row| {
+                self.row(row)
+                    .iter()
+                    .zip(vector)
+                    .fold(T::default(), |acc, (a, b)| acc + *a * *b)
+            })
+            .collect()
+    }
+
+    pub fn identity(size: usize) -> Self
+    where
+        T: From<i8>,
+    {
+        let mut identity = Self::new(size, size);
+        for i in 0..size {
+            identity[(i, i)] = T::from(1);
+        }
+
+        identity
+    }
+
+    // Square and multiply, handy for stepping a linear recurrence far ahead.
+    pub fn pow(&self, exponent: u64) -> Self
+    where
+        T: From<i8>,
+    {
+        assert_eq!(self.rows, self.cols, "Matrix::pow: matrix is not square");
+
+        let mut result = Self::identity(self.rows);
+        let mut base = self.clone();
+        let mut exponent = exponent;
+
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = &result * &base;
+            }
+
+            base = &base * &base;
+            exponent >>= 1;
+        }
+
+        result
+    }
+}
+
+impl<T> Mul for &Matrix<T>
+where
+    T: Copy + Default + Add<Output = T> + Mul<Output = T>,
+{
+    type Output = Matrix<T>;
+
+    fn mul(self, other: Self) -> Matrix<T> {
+        assert_eq!(self.cols, other.rows, "Matrix::mul: dimension mismatch");
+
+        let mut product = Matrix::new(self.rows, other.cols);
+
+        for row in 0..self.rows {
+            for k in 0..self.cols {
+                let a = self[(row, k)];
+                for col in 0..other.cols {
+                    product[(row, col)] = product[(row, col)] + a * other[(k, col)];
+                }
+            }
+        }
+
+        product
+    }
+}
+
+impl<T> Index<(usize, usize)> for Matrix<T> {
+    type Output = T;
+
+    fn index(&self, (row, col): (usize, usize)) -> &T {
+        assert!(
+            row < self.rows && col < self.cols,
+            "({row}, {col}) out of bounds"
+        );
+
+        &self.data[row * self.cols + col]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Matrix<T> {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut T {
+        assert!(
+            row < self.rows && col < self.cols,
+            "({row}, {col}) out of bounds"
+        );
+
+        &mut self.data[row * self.cols + col]
+    }
+}
+
+#[allow(dead_code)]
+impl Matrix<i128> {
+    // Bareiss elimination, every division is exact so the entries stay integers. Returns the
+    // echelon form, the rank and whether an odd number of row swaps was made.
+    fn fraction_free_echelon(&self) -> (Self, usize, bool) {
+        let mut m = self.clone();
+        let mut rank = 0;
+        let mut previous_pivot = 1;
+        let mut swapped = false;
+
+        for col in 0..m.cols {
+            if rank == m.rows {
+                break;
+            }
+
+            let Some(pivot_row) = (rank..m.rows).find(|row| m[(*row, col)] != 0) else {
+                continue;
+            };
+
+            if pivot_row != rank {
+                m.swap_rows(pivot_row, rank);
+                swapped = !swapped;
+            }
+
+            let pivot = m[(rank, col)];
+            for row in rank + 1..m.rows {
+                let factor = m[(row, col)];
+                for c in col..m.cols {
+                    m[(row, c)] = (pivot * m[(row, c)] - factor * m[(rank, c)]) / previous_pivot;
+                }
+            }
+
+            previous_pivot = pivot;
+            rank += 1;
+        }
+
+        (m, rank, swapped)
+    }
+
+    pub fn rank(&self) -> usize {
+        self.fraction_free_echelon().1
+    }
+
+    pub fn determinant(&self) -> i128 {
+        assert_eq!(
+            self.rows, self.cols,
+            "Matrix::determinant: matrix is not square"
+        );
+
+        if self.rows == 0 {
+            return 1;
+        }
+
+        let (echelon, rank, swapped) = self.fraction_free_echelon();
+        if rank < self.rows {
+            return 0;
+        }
+
+        let determinant = echelon[(self.rows - 1, self.cols - 1)];
+        if swapped { -determinant } else { determinant }
+    }
+
+    pub fn to_rational(&self) -> Matrix<Rational> {
+        self.map(Rational::from)
+    }
+}
+
+#[allow(dead_code)]
+impl Matrix<Rational> {
+    // Reduced row echelon form along with the column of each pivot.
+    pub fn reduced_row_echelon(&self) -> (Self, Vec<usize>) {
+        let mut m = self.clone();
+        let mut pivots = vec![];
+
+        for col in 0..m.cols {
+            let rank = pivots.len();
+            if rank == m.rows {
+                break;
+            }
+
+            let Some(pivot_row) = (rank..m.rows).find(|row| !m[(*row, col)].is_zero()) else {
+                continue;
+            };
+            m.swap_rows(pivot_row, rank);
+
+            let pivot = m[(rank, col)];
+            for c in col..m.cols {
+                m[(rank, c)] /= pivot;
+            }
+
+            for row in (0..m.rows).filter(|row| *row != rank) {
+                let factor = m[(row, col)];
+                if factor.is_zero() {
+                    continue;
+                }
+
+                for c in col..m.cols {
+                    let delta = factor * m[(rank, c)];
+                    m[(row, c)] -= delta;
+                }
+            }
+
+            pivots.push(col);
+        }
+
+        (m, pivots)
+    }
+
+    // A basis of the vectors x with self * x = 0, one per free column.
+    pub fn null_space(&self) -> Vec<Vec<Rational>> {
+        let (reduced, pivots) = self.reduced_row_echelon();
+
+        (0..self.cols)
+            .filter(|col| !pivots.contains(col))
+            .map(|free| {
+                let mut basis = vec![Rational::zero(); self.cols];
+                basis[free] = Rational::one();
+
+                for (row, pivot) in pivots.iter().enumerate() {
+                    basis[*pivot] = -reduced[(row, free)];
+                }
+
+                basis
+            })
+            .collect()
+    }
+}
+
+// Every solution of a linear system, as a particular solution plus any combination of the
+// free variables. Pivot variables are found by back substitution from the free ones.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct LinearSolution {
+    reduced: Matrix<Rational>,
+    pub pivots: Vec<usize>,
+    pub free: Vec<usize>,
+}
+
+#[allow(dead_code)]
+impl LinearSolution {
+    // Solves a * x = b, None if the system is inconsistent.
+    pub fn solve(a: &Matrix<i128>, b: &[i128]) -> Option<Self> {
+        assert_eq!(a.rows, b.len(), "LinearSolution::solve: length mismatch");
+
+        let mut augmented = Matrix::new(a.rows, a.cols + 1);
+        for row in 0..a.rows {
+            for col in 0..a.cols {
+                augmented[(row, col)] = Rational::from(a[(row, col)]);
+            }
+            augmented[(row, a.cols)] = Rational::from(b[row]);
+        }
+
+        let (reduced, pivots) = augmented.reduced_row_echelon();
+
+        // a pivot in the augmented column means 0 = non-zero
+        if pivots.last() == Some(&a.cols) {
+            return None;
+        }
+
+        let free = (0..a.cols).filter(|col| !pivots.contains(col)).collect();
+
+        Some(Self {
+            reduced,
+            pivots,
+            free,
+        })
+    }
+
+    pub fn is_unique(&self) -> bool {
+        self.free.is_empty()
+    }
+
+    // The solution with the free variables set to `free_values`, in the order of `free`.
+    pub fn evaluate(&self, free_values: &[Rational]) -> Vec<Rational> {
+        assert_eq!(free_values.len(), self.free.len());
+
+        let cols = self.reduced.cols - 1;
+        let mut solution = vec![Rational::zero(); cols];
+
+        for (col, value) in self.free.iter().zip(free_values) {
+            solution[*col] = *value;
+        }
+
+        for (row, pivot) in self.pivots.iter().enumerate() {
+            let mut value = self.reduced[(row, cols)];
+            for (col, free_value) in self.free.iter().zip(free_values) {
+                value -= self.reduced[(row, *col)] * *free_value;
+            }
+
+            solution[*pivot] = value;
+        }
+
+        solution
+    }
+
+    // Every solution in non-negative integers with x[i] <= upper_bounds[i], found by trying
+    // each assignment of the free variables within their bounds.
+    pub fn bounded_integer_solutions(
+        &self,
+        upper_bounds: &[i128],
+    ) -> impl Iterator<Item = Vec<i128>> + '_ {
+        let upper_bounds = upper_bounds.to_vec();
+        let free_bounds = self
+            .free
+            .iter()
+            .map(|col| upper_bounds[*col])
+            .collect::<Vec<_>>();
+
+        let mut next = if free_bounds.iter().all(|bound| *bound >= 0) {
+            Some(vec![0; self.free.len()])
+        } else {
+            None
+        };
+
+        std::iter::from_fn(move || {
+            loop {
+                let current = next.take()?;
+                next = Self::advance(&current, &free_bounds);
+
+                let free_values = current
+                    .iter()
+                    .map(|v| Rational::from(*v))
+                    .collect::<Vec<_>>();
+                let solution = self
+                    .evaluate(&free_values)
+                    .iter()
+                    .zip(upper_bounds.iter())
+                    .map(|(value, bound)| {
+                        value
+                            .to_integer()
+                            .filter(|value| (0..=*bound).contains(value))
+                    })
+                    .collect::<Option<Vec<_>>>();
+
+                if solution.is_some() {
+                    return solution;
+                }
+            }
+        })
+    }
+
+    // Counts up like an odometer, None once every assignment has been visited.
+    fn advance(current: &[i128], bounds: &[i128]) -> Option<Vec<i128>> {
+        let mut next = current.to_vec();
+
+        for (value, bound) in next.iter_mut().zip(bounds) {
+            if *value < *bound {
+                *value += 1;
+                return Some(next);
+            }
+
+            *value = 0;
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_mul_and_pow() {
+        let a = Matrix::from_rows(&[[1, 2, 3], [4, 5, 6]]);
+        let b = Matrix::from_rows(&[[7, 8], [9, 10], [11, 12]]);
+
+        assert_eq!(&a * &b, Matrix::from_rows(&[[58, 64], [139, 154]]));
+        assert_eq!(a.transpose(), Matrix::from_rows(&[[1, 4], [2, 5], [3, 6]]));
+        assert_eq!(a.mul_vec(&[1, 0, -1]), [-2, -2]);
+
+        // fibonacci as a linear recurrence
+        let fib = Matrix::from_rows(&[[1_i128, 1], [1, 0]]);
+        assert_eq!(fib.pow(0), Matrix::identity(2));
+        assert_eq!(fib.pow(90)[(0, 1)], 2_880_067_194_370_816_120);
+    }
+
+    #[test]
+    fn test_rank_and_determinant() {
+        let m = Matrix::<i128>::from_rows(&[[2, -1, 0], [-1, 2, -1], [0, -1, 2]]);
+        assert_eq!(m.rank(), 3);
+        assert_eq!(m.determinant(), 4);
+
+        let m = Matrix::<i128>::from_rows(&[[0, 1], [1, 0]]);
+        assert_eq!(m.determinant(), -1);
+
+        let m = Matrix::<i128>::from_rows(&[[1, 2, 3], [2, 4, 6], [1, 0, 1]]);
+        assert_eq!(m.rank(), 2);
+        assert_eq!(m.determinant(), 0);
+    }
+
+    #[test]
+    fn test_null_space() {
+        let m = Matrix::<i128>::from_rows(&[[1, 2, 3], [2, 4, 6], [1, 0, 1]]).to_rational();
+        let null_space = m.null_space();
+
+        assert_eq!(null_space.len(), 1);
+        let zero = m.mul_vec(&null_space[0]);
+        assert!(zero.iter().all(|value| value.is_zero()));
+        assert_eq!(
+            null_space[0],
+            [Rational::from(-1), Rational::from(-1), Rational::from(1)]
+        );
+    }
+
+    #[test]
+    fn test_solve() {
+        let a = Matrix::<i128>::from_rows(&[[2, 1], [1, 3]]);
+        let solution = LinearSolution::solve(&a, &[3, 5]).unwrap();
+
+        assert!(solution.is_unique());
+        assert_eq!(
+            solution.evaluate(&[]),
+            [Rational::new(4, 5), Rational::new(7, 5)]
+        );
+
+        let a = Matrix::<i128>::from_rows(&[[1, 1], [2, 2]]);
+        assert_eq!(LinearSolution::solve(&a, &[1, 3]), None);
+    }
+
+    #[test]
+    fn test_bounded_integer_solutions() {
+        // 2025 day 10, first machine: buttons (3) (1,3) (2) (2,3) (0,2) (0,1), joltage {3,5,4,7}
+        let buttons: [&[usize]; 6] = [&[3], &[1, 3], &[2], &[2, 3], &[0, 2], &[0, 1]];
+        let targets = [3, 5, 4, 7];
+
+        let mut a = Matrix::<i128>::new(targets.len(), buttons.len());
+        for (col, button) in buttons.iter().enumerate() {
+            for row in button.iter() {
+                a[(*row, col)] = 1;
+            }
+        }
+
+        let bounds = buttons
+            .iter()
+            .map(|button| button.iter().map(|row| targets[*row]).min().unwrap())
+            .collect::<Vec<_>>();
+
+        let solution = LinearSolution::solve(&a, &targets).unwrap();
+        assert_eq!(solution.free.len(), 2);
+
+        let solutions = solution
+            .bounded_integer_solutions(&bounds)
+            .collect::<Vec<_>>();
+        assert!(solutions.iter().all(|x| a.mul_vec(x) == targets));
+
+        let fewest = solutions.iter().map(|x| x.iter().sum::<i128>()).min();
+        assert_eq!(fewest, Some(10));
+    }
+}
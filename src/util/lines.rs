@@ -0,0 +1,255 @@
+use super::point::{Point, Point3};
+use super::rational::Rational;
+
+// Lines are written as `origin + t * direction`, so the parameter doubles as the time for
+// things moving at a constant velocity. Everything is computed exactly with rationals.
+
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub struct Line2 {
+    pub origin: Point<i128>,
+    pub direction: Point<i128>,
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub struct Line3 {
+    pub origin: Point3<i128>,
+    pub direction: Point3<i128>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Intersection<P> {
+    // The lines meet once, at `a.origin + t * a.direction` and `b.origin + u * b.direction`.
+    At { t: Rational, u: Rational, point: P },
+    Parallel,
+    // The same line, every point is shared.
+    Coincident,
+    // Not parallel but never meeting, only possible in 3D.
+    Skew,
+}
+
+fn cross2(a: Point<i128>, b: Point<i128>) -> i128 {
+    a.x * b.y - a.y * b.x
+}
+
+fn cross3(a: Point3<i128>, b: Point3<i128>) -> Point3<i128> {
+    Point3::new(
+        a.y * b.z - a.z * b.y,
+        a.z * b.x - a.x * b.z,
+        a.x * b.y - a.y * b.x,
+    )
+}
+
+fn dot3(a: Point3<i128>, b: Point3<i128>) -> i128 {
+    a.x * b.x + a.y * b.y + a.z * b.z
+}
+
+#[allow(dead_code)]
+impl Line2 {
+    pub fn new(origin: Point<i128>, direction: Point<i128>) -> Self {
+        Self { origin, direction }
+    }
+
+    pub fn through(a: Point<i128>, b: Point<i128>) -> Self {
+        Self::new(a, b - a)
+    }
+
+    pub fn at(&self, t: Rational) -> Point<Rational> {
+        Point::new(
+            Rational::from(self.origin.x) + t * Rational::from(self.direction.x),
+            Rational::from(self.origin.y) + t * Rational::from(self.direction.y),
+        )
+    }
+
+    pub fn intersect(&self, other: &Self) -> Intersection<Point<Rational>> {
+        let offset = other.origin - self.origin;
+        let denom = cross2(self.direction, other.direction);
+
+        if denom == 0 {
+            return if cross2(offset, self.direction) == 0 {
+                Intersection::Coincident
+            } else {
+                Intersection::Parallel
+            };
+        }
+
+        let t = Rational::new(cross2(offset, other.direction), denom);
+        let u = Rational::new(cross2(offset, self.direction), denom);
+
+        Intersection::At {
+            t,
+            u,
+            point: self.at(t),
+        }
+    }
+}
+
+#[allow(dead_code)]
+impl Line3 {
+    pub fn new(origin: Point3<i128>, direction: Point3<i128>) -> Self {
+        Self { origin, direction }
+    }
+
+    pub fn through(a: Point3<i128>, b: Point3<i128>) -> Self {
+        Self::new(a, b - a)
+    }
+
+    pub fn at(&self, t: Rational) -> Point3<Rational> {
+        Point3::new(
+            Rational::from(self.origin.x) + t * Rational::from(self.direction.x),
+            Rational::from(self.origin.y) + t * Rational::from(self.direction.y),
+            Rational::from(self.origin.z) + t * Rational::from(self.direction.z),
+        )
+    }
+
+    pub fn intersect(&self, other: &Self) -> Intersection<Point3<Rational>> {
+        let offset = other.origin - self.origin;
+        let normal = cross3(self.direction, other.direction);
+
+        if normal == Point3::default() {
+            return if cross3(offset, self.direction) == Point3::default() {
+                Intersection::Coincident
+            } else {
+                Intersection::Parallel
+            };
+        }
+
+        if dot3(offset, normal) != 0 {
+            return Intersection::Skew;
+        }
+
+        let denom = dot3(normal, normal);
+        let t = Rational::new(dot3(cross3(offset, other.direction), normal), denom);
+        let u = Rational::new(dot3(cross3(offset, self.direction), normal), denom);
+
+        Intersection::At {
+            t,
+            u,
+            point: self.at(t),
+        }
+    }
+}
+
+// Whether the segments `a0`-`a1` and `b0`-`b1` share at least one point, end points included.
+#[allow(dead_code)]
+pub fn segments_intersect(
+    a0: Point<i128>,
+    a1: Point<i128>,
+    b0: Point<i128>,
+    b1: Point<i128>,
+) -> bool {
+    let on_segment = |t: Rational| Rational::zero() <= t && t <= Rational::one();
+
+    match Line2::through(a0, a1).intersect(&Line2::through(b0, b1)) {
+        Intersection::At { t, u, .. } => on_segment(t) && on_segment(u),
+        Intersection::Coincident => {
+            // project onto the longer axis of `a` and compare the ranges
+            let key = |p: Point<i128>| {
+                if (a1.x - a0.x).abs() >= (a1.y - a0.y).abs() {
+                    p.x
+                } else {
+                    p.y
+                }
+            };
+            let (a_min, a_max) = (key(a0).min(key(a1)), key(a0).max(key(a1)));
+            let (b_min, b_max) = (key(b0).min(key(b1)), key(b0).max(key(b1)));
+
+            a_min <= b_max && b_min <= a_max
+        }
+        Intersection::Parallel | Intersection::Skew => false,
+    }
+}
+
+// Whether `point` lies in the box from `min` to `max`, both inclusive.
+pub fn within_box(point: &Point<Rational>, min: &Point<Rational>, max: &Point<Rational>) -> bool {
+    (min.x..=max.x).contains(&point.x) && (min.y..=max.y).contains(&point.y)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn p(x: i128, y: i128) -> Point<i128> {
+        Point::new(x, y)
+    }
+
+    #[test]
+    fn test_intersect_2d() {
+        // hailstones A and B from 2023 day 24
+        let a = Line2::new(p(19, 13), p(-2, 1));
+        let b = Line2::new(p(18, 19), p(-1, -1));
+
+        let Intersection::At { t, u, point } = a.intersect(&b) else {
+            panic!("expected the paths to cross");
+        };
+
+        assert_eq!(
+            point,
+            Point::new(Rational::new(43, 3), Rational::new(46, 3))
+        );
+        assert_eq!(t, Rational::new(7, 3));
+        assert_eq!(u, Rational::new(11, 3));
+        assert_eq!(b.at(u), point);
+
+        let c = Line2::new(p(20, 25), p(-2, -2));
+        assert_eq!(b.intersect(&c), Intersection::Parallel);
+
+        let d = Line2::new(p(16, 17), p(3, 3));
+        assert_eq!(b.intersect(&d), Intersection::Coincident);
+    }
+
+    #[test]
+    fn test_segments_intersect() {
+        for (a0, a1, b0, b1, expected) in [
+            (p(0, 0), p(4, 4), p(0, 4), p(4, 0), true),
+            (p(0, 0), p(1, 1), p(0, 4), p(4, 0), false),
+            (p(0, 0), p(2, 2), p(2, 2), p(5, 0), true),
+            (p(0, 0), p(4, 0), p(3, 0), p(6, 0), true),
+            (p(0, 0), p(2, 0), p(3, 0), p(6, 0), false),
+            (p(0, 0), p(0, 2), p(1, 0), p(1, 2), false),
+        ] {
+            assert_eq!(
+                segments_intersect(a0, a1, b0, b1),
+                expected,
+                "{a0:?}-{a1:?} {b0:?}-{b1:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_intersect_3d() {
+        let a = Line3::new(Point3::new(0, 0, 0), Point3::new(1, 1, 1));
+        let b = Line3::new(Point3::new(4, 0, 2), Point3::new(-1, 1, 0));
+
+        assert_eq!(
+            a.intersect(&b),
+            Intersection::At {
+                t: Rational::from(2),
+                u: Rational::from(2),
+                point: Point3::new(Rational::from(2), Rational::from(2), Rational::from(2)),
+            }
+        );
+
+        let skew = Line3::new(Point3::new(0, 0, 5), Point3::new(1, -1, 0));
+        assert_eq!(a.intersect(&skew), Intersection::Skew);
+
+        let parallel = Line3::new(Point3::new(1, 0, 0), Point3::new(2, 2, 2));
+        assert_eq!(a.intersect(&parallel), Intersection::Parallel);
+
+        let same = Line3::through(Point3::new(-1, -1, -1), Point3::new(3, 3, 3));
+        assert_eq!(a.intersect(&same), Intersection::Coincident);
+    }
+
+    #[test]
+    fn test_within_box() {
+        let min = Point::new(Rational::from(7), Rational::from(7));
+        let max = Point::new(Rational::from(27), Rational::from(27));
+
+        let inside = Point::new(Rational::new(43, 3), Rational::new(46, 3));
+        let outside = Point::new(Rational::new(-2, 1), Rational::new(3, 1));
+
+        assert!(within_box(&inside, &min, &max));
+        assert!(!within_box(&outside, &min, &max));
+        assert!(within_box(&max, &min, &max));
+    }
+}
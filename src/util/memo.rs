@@ -0,0 +1,203 @@
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+use std::ops::Add;
+
+// A cache for one run of a recursive solver. It is passed down the recursion rather than
+// living in a global, so every call to the solver starts fresh and the cache is dropped with
+// it. Lookups take a borrowed key, the owned key is only built on a miss.
+#[derive(Debug, Clone)]
+pub struct Memo<K, V> {
+    cache: HashMap<K, V>,
+    hits: usize,
+    misses: usize,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct MemoStats {
+    pub hits: usize,
+    pub misses: usize,
+    pub size: usize,
+}
+
+#[allow(dead_code)]
+impl<K, V> Memo<K, V>
+where
+    K: Hash + Eq,
+    V: Clone,
+{
+    pub fn new() -> Self {
+        Self {
+            cache: HashMap::new(),
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    // The cached value for `key`, otherwise computes it with `compute`, which gets the memo
+    // back so it can recurse.
+    pub fn get_or_insert_with<Q, F>(&mut self, key: &Q, compute: F) -> V
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = K> + ?Sized,
+        F: FnOnce(&mut Self) -> V,
+    {
+        if let Some(value) = self.cache.get(key) {
+            self.hits += 1;
+            return value.clone();
+        }
+
+        self.misses += 1;
+        let value = compute(self);
+        self.cache.insert(key.to_owned(), value.clone());
+
+        value
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.cache.get(key)
+    }
+
+    pub fn insert(&mut self, key: K, value: V) {
+        self.cache.insert(key, value);
+    }
+
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.cache.clear();
+        self.hits = 0;
+        self.misses = 0;
+    }
+
+    pub fn stats(&self) -> MemoStats {
+        MemoStats {
+            hits: self.hits,
+            misses: self.misses,
+            size: self.cache.len(),
+        }
+    }
+}
+
+impl<K, V> Default for Memo<K, V>
+where
+    K: Hash + Eq,
+    V: Clone,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[allow(dead_code)]
+impl MemoStats {
+    // The fraction of lookups answered from the cache.
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            0.0
+        } else {
+            self.hits as f64 / lookups as f64
+        }
+    }
+}
+
+// Combines the statistics of several caches, like one per line of a puzzle.
+impl Add for MemoStats {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            hits: self.hits + other.hits,
+            misses: self.misses + other.misses,
+            size: self.size + other.size,
+        }
+    }
+}
+
+impl fmt::Display for MemoStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "hits: {}, misses: {}, size: {}, hit rate: {:.1}%",
+            self.hits,
+            self.misses,
+            self.size,
+            self.hit_rate() * 100.0
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn fibonacci(memo: &mut Memo<u64, u64>, n: u64) -> u64 {
+        if n < 2 {
+            return n;
+        }
+
+        memo.get_or_insert_with(&n, |memo| fibonacci(memo, n - 1) + fibonacci(memo, n - 2))
+    }
+
+    // how many ways `target` can be built by joining `towels`, keyed on the remaining suffix
+    fn arrangements(memo: &mut Memo<String, usize>, towels: &[&str], target: &str) -> usize {
+        if target.is_empty() {
+            return 1;
+        }
+
+        memo.get_or_insert_with(target, |memo| {
+            towels
+                .iter()
+                .filter_map(|towel| target.strip_prefix(towel))
+                .map(|rest| arrangements(memo, towels, rest))
+                .sum()
+        })
+    }
+
+    #[test]
+    fn test_recursion() {
+        let mut memo = Memo::new();
+        assert_eq!(fibonacci(&mut memo, 90), 2_880_067_194_370_816_120);
+        assert_eq!(
+            memo.stats(),
+            MemoStats {
+                hits: 87,
+                misses: 89,
+                size: 89
+            }
+        );
+
+        let twice = memo.stats() + memo.stats();
+        assert_eq!((twice.hits, twice.misses, twice.size), (174, 178, 178));
+    }
+
+    #[test]
+    fn test_borrowed_keys() {
+        let towels = ["r", "wr", "b", "g", "bwu", "rb", "gb", "br"];
+        let mut memo = Memo::new();
+
+        assert_eq!(arrangements(&mut memo, &towels, "rrbgbr"), 6);
+        assert!(memo.get("gbr").is_some());
+        assert!(memo.stats().hits > 0);
+
+        memo.clear();
+        assert!(memo.is_empty());
+        assert_eq!(
+            memo.stats().to_string(),
+            "hits: 0, misses: 0, size: 0, hit rate: 0.0%"
+        );
+    }
+}
@@ -1,3 +1,22 @@
+pub mod bit_grid;
+pub mod combinatorics;
+pub mod cube_net;
+pub mod cycle;
+pub mod dsu;
+pub mod geometry;
+pub mod graph;
 pub mod grid;
+pub mod grid_render;
 pub mod grid_v1;
+pub mod hex;
+pub mod ilp;
+pub mod intervals;
+pub mod linalg;
+pub mod lines;
+pub mod memo;
+pub mod modular_aritmatic;
+pub mod number_theory;
 pub mod point;
+pub mod rational;
+pub mod vm;
+pub mod voxel;
@@ -1,4 +1,7 @@
-use std::ops::Add;
+use std::fmt;
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use super::number_theory::{mod_pow, mul_mod};
 
 #[allow(dead_code)]
 pub trait ModularAdd: Sized + Add<Self, Output = Self> {
@@ -12,51 +15,155 @@ pub trait ModularSub: Sized + Add<Self, Output = Self> {
     fn modular_dec(&mut self, modulus: Self);
 }
 
-impl ModularAdd for usize {
-    fn modular_add(&self, val: Self, modulus: Self) -> Self {
-        (*self + val) % modulus
+// Both operands are reduced first so the result is always in [0, modulus) and
+// the intermediate values can not overflow.
+macro_rules! impl_modular_ops {
+    ($($t:ty),*) => {
+        $(
+            impl ModularAdd for $t {
+                fn modular_add(&self, val: Self, modulus: Self) -> Self {
+                    let a = self.rem_euclid(modulus);
+                    let b = val.rem_euclid(modulus);
+
+                    if a >= modulus - b {
+                        a - (modulus - b)
+                    } else {
+                        a + b
+                    }
+                }
+
+                fn modular_inc(&mut self, modulus: Self) {
+                    *self = self.modular_add(1, modulus);
+                }
+            }
+
+            impl ModularSub for $t {
+                fn modular_sub(&self, val: Self, modulus: Self) -> Self {
+                    let a = self.rem_euclid(modulus);
+                    let b = val.rem_euclid(modulus);
+
+                    if a >= b {
+                        a - b
+                    } else {
+                        modulus - (b - a)
+                    }
+                }
+
+                fn modular_dec(&mut self, modulus: Self) {
+                    *self = self.modular_sub(1, modulus)
+                }
+            }
+        )*
+    };
+}
+
+impl_modular_ops!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize
+);
+
+// Any primitive integer reduced into [0, modulus), for building a `Mod` from whatever type
+// a puzzle happens to count in.
+pub trait Residue {
+    fn residue(self, modulus: u64) -> u64;
+}
+
+// Every unsigned type fits in u128 and every signed one in i128, so widening first means
+// nothing is truncated before it is reduced.
+macro_rules! impl_residue {
+    (unsigned: $($u:ty),*; signed: $($i:ty),*) => {
+        $(
+            impl Residue for $u {
+                fn residue(self, modulus: u64) -> u64 {
+                    (self as u128 % modulus as u128) as u64
+                }
+            }
+        )*
+        $(
+            impl Residue for $i {
+                fn residue(self, modulus: u64) -> u64 {
+                    (self as i128).rem_euclid(modulus as i128) as u64
+                }
+            }
+        )*
+    };
+}
+
+impl_residue!(
+    unsigned: u8, u16, u32, u64, u128, usize;
+    signed: i8, i16, i32, i64, i128, isize
+);
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Copy, Clone, Default)]
+pub struct Mod<const M: u64>(u64);
+
+#[allow(dead_code)]
+impl<const M: u64> Mod<M> {
+    pub fn new<T: Residue>(value: T) -> Self {
+        Self(value.residue(M))
     }
 
-    fn modular_inc(&mut self, modulus: Self) {
-        *self = self.modular_add(1, modulus);
+    pub fn value(self) -> u64 {
+        self.0
+    }
+
+    pub fn pow(self, exponent: u64) -> Self {
+        Self(mod_pow(self.0, exponent, M))
     }
 }
 
-impl ModularSub for usize {
-    fn modular_sub(&self, val: Self, modulus: Self) -> Self {
-        if *self >= val {
-            (*self - val) % modulus
-        } else {
-            (modulus + *self - val) % modulus
-        }
+impl<const M: u64> Add for Mod<M> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self(self.0.modular_add(other.0, M))
     }
+}
+
+impl<const M: u64> Sub for Mod<M> {
+    type Output = Self;
 
-    fn modular_dec(&mut self, modulus: Self) {
-        *self = self.modular_sub(1, modulus)
+    fn sub(self, other: Self) -> Self {
+        Self(self.0.modular_sub(other.0, M))
     }
 }
 
-impl ModularAdd for u32 {
-    fn modular_add(&self, val: Self, modulus: Self) -> Self {
-        (*self + val) % modulus
+impl<const M: u64> Mul for Mod<M> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        Self(mul_mod(self.0, other.0, M))
     }
+}
 
-    fn modular_inc(&mut self, modulus: Self) {
-        *self = self.modular_add(1, modulus);
+impl<const M: u64> Neg for Mod<M> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self(0.modular_sub(self.0, M))
     }
 }
 
-impl ModularSub for u32 {
-    fn modular_sub(&self, val: Self, modulus: Self) -> Self {
-        if *self >= val {
-            *self - (val % modulus)
-        } else {
-            (modulus + *self - (val % modulus)) % modulus
-        }
+impl<const M: u64> AddAssign for Mod<M> {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl<const M: u64> SubAssign for Mod<M> {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl<const M: u64> MulAssign for Mod<M> {
+    fn mul_assign(&mut self, other: Self) {
+        *self = *self * other;
     }
+}
 
-    fn modular_dec(&mut self, modulus: Self) {
-        *self = self.modular_sub(1, modulus)
+impl<const M: u64> fmt::Display for Mod<M> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
     }
 }
 
@@ -90,6 +197,30 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_modular_add_overflow() {
+        assert_eq!(250_u8.modular_add(10, 251), 9);
+        assert_eq!(u64::MAX.modular_add(u64::MAX, u64::MAX - 1), 2);
+    }
+
+    #[test]
+    fn test_modular_add_signed() {
+        for (val, magnitude, modulus, expected) in [
+            (52_i32, 48_i32, 100_i32, 0_i32),
+            (50_i32, -68_i32, 100_i32, 82_i32),
+            (-5_i32, 0_i32, 100_i32, 95_i32),
+            (-250_i32, -250_i32, 100_i32, 0_i32),
+            (i32::MAX, i32::MAX, 7_i32, 2_i32),
+        ] {
+            let actual = val.modular_add(magnitude, modulus);
+            assert_eq!(actual, expected);
+        }
+
+        let mut val = -1_i64;
+        val.modular_inc(10);
+        assert_eq!(val, 0);
+    }
+
     #[test]
     fn test_modular_sub_usize() {
         for (val, magnitude, modulus, expected) in [
@@ -117,9 +248,53 @@ mod tests {
             (99_u32, 99_u32, 100_u32, 0_u32),
             (14_u32, 82_u32, 100_u32, 32_u32),
             (85_u32, 4404_u32, 100_u32, 81_u32),
+            (150_u32, 20_u32, 100_u32, 30_u32),
         ] {
             let actual = val.modular_sub(magnitude, modulus);
             assert_eq!(actual, expected);
         }
     }
+
+    #[test]
+    fn test_modular_sub_signed() {
+        for (val, magnitude, modulus, expected) in [
+            (50_i64, 68_i64, 100_i64, 82_i64),
+            (-5_i64, 5_i64, 100_i64, 90_i64),
+            (5_i64, -5_i64, 100_i64, 10_i64),
+            (i64::MIN, i64::MAX, 10_i64, 5_i64),
+        ] {
+            let actual = val.modular_sub(magnitude, modulus);
+            assert_eq!(actual, expected);
+        }
+
+        let mut val = 0_i8;
+        val.modular_dec(10);
+        assert_eq!(val, 9);
+    }
+
+    #[test]
+    fn test_mod_ops() {
+        type Dial = Mod<100>;
+
+        let mut dial = Dial::new(50);
+        dial += Dial::new(-68);
+        assert_eq!(dial.value(), 82);
+
+        dial -= Dial::new(30);
+        assert_eq!(dial, Dial::new(52));
+
+        assert_eq!(-Dial::new(1), Dial::new(99));
+        assert_eq!(-Dial::new(0), Dial::new(0));
+        assert_eq!(Dial::new(12) * Dial::new(-11), Dial::new(68));
+        assert_eq!(Dial::new(-1000), Dial::new(0));
+
+        type Big = Mod<1_000_000_007>;
+        assert_eq!(Big::new(2).pow(1_000_000_006), Big::new(1));
+        assert_eq!((Big::new(-1) * Big::new(-1)).to_string(), "1");
+
+        assert_eq!(Dial::new(250_usize), Dial::new(50));
+        assert_eq!(Dial::new(-250_isize), Dial::new(50));
+        assert_eq!(Dial::new(u128::MAX), Dial::new(55));
+        assert_eq!(Big::new(u64::MAX).value(), 582_344_007);
+    }
 }
@@ -0,0 +1,246 @@
+use std::ops::{Div, Mul, Rem};
+
+pub fn least_common_multiple<T>(a: T, b: T) -> T
+where
+    T: Ord + Rem<Output = T> + Mul<Output = T> + Div<Output = T> + Default + Copy,
+{
+    a / greatest_common_divisor(a, b) * b
+}
+
+pub fn greatest_common_divisor<T>(a: T, b: T) -> T
+where
+    T: Ord + Rem<Output = T> + Mul<Output = T> + Div<Output = T> + Default + Copy,
+{
+    let (mut a, mut b) = (a, b);
+
+    while b != T::default() {
+        let remainder = a % b;
+        a = b;
+        b = remainder;
+    }
+
+    a
+}
+
+// Returns (g, x, y) such that a * x + b * y = g = gcd(a, b), with g >= 0.
+#[allow(dead_code)]
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_s, mut s) = (1, 0);
+    let (mut old_t, mut t) = (0, 1);
+
+    while r != 0 {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_s, s) = (s, old_s - quotient * s);
+        (old_t, t) = (t, old_t - quotient * t);
+    }
+
+    if old_r < 0 {
+        (-old_r, -old_s, -old_t)
+    } else {
+        (old_r, old_s, old_t)
+    }
+}
+
+#[allow(dead_code)]
+pub fn mod_inverse(a: i128, modulus: i128) -> Option<i128> {
+    let (g, x, _) = extended_gcd(a.rem_euclid(modulus), modulus);
+
+    if g == 1 {
+        Some(x.rem_euclid(modulus))
+    } else {
+        None
+    }
+}
+
+// Combines congruences x = residue (mod modulus) into a single (residue, modulus) pair.
+// The moduli do not need to be coprime, None is returned if the system has no solution.
+#[allow(dead_code)]
+pub fn chinese_remainder(congruences: &[(i128, i128)]) -> Option<(i128, i128)> {
+    let mut residue = 0;
+    let mut modulus = 1;
+
+    for &(r, m) in congruences {
+        let r = r.rem_euclid(m);
+        let (g, _, _) = extended_gcd(modulus, m);
+
+        if (r - residue) % g != 0 {
+            return None;
+        }
+
+        let m_reduced = m / g;
+        let inverse = mod_inverse(modulus / g, m_reduced)?;
+        let t = mul_mod_i128((r - residue) / g, inverse, m_reduced);
+
+        let combined = modulus * m_reduced;
+        residue = (residue + mul_mod_i128(modulus, t, combined)).rem_euclid(combined);
+        modulus = combined;
+    }
+
+    Some((residue, modulus))
+}
+
+#[allow(dead_code)]
+pub fn mul_mod(a: u64, b: u64, modulus: u64) -> u64 {
+    ((a as u128 * b as u128) % modulus as u128) as u64
+}
+
+// Double and add so that the intermediate values never leave [0, modulus).
+#[allow(dead_code)]
+pub fn mul_mod_i128(a: i128, b: i128, modulus: i128) -> i128 {
+    let add_mod = |x: i128, y: i128| {
+        if x >= modulus - y {
+            x - (modulus - y)
+        } else {
+            x + y
+        }
+    };
+
+    let mut a = a.rem_euclid(modulus);
+    let mut b = b.rem_euclid(modulus);
+    let mut product = 0;
+
+    while b > 0 {
+        if b & 1 == 1 {
+            product = add_mod(product, a);
+        }
+
+        a = add_mod(a, a);
+        b >>= 1;
+    }
+
+    product
+}
+
+#[allow(dead_code)]
+pub fn mod_pow(base: u64, exponent: u64, modulus: u64) -> u64 {
+    if modulus == 1 {
+        return 0;
+    }
+
+    let mut base = base % modulus;
+    let mut exponent = exponent;
+    let mut result = 1;
+
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = mul_mod(result, base, modulus);
+        }
+
+        base = mul_mod(base, base, modulus);
+        exponent >>= 1;
+    }
+
+    result
+}
+
+// Largest x with x * x <= n, by Newton's method from a starting guess above the root.
+pub fn integer_sqrt(n: u64) -> u64 {
+    if n < 2 {
+        return n;
+    }
+
+    let mut x = 1 << (u64::BITS - n.leading_zeros()).div_ceil(2);
+    loop {
+        let y = (x + n / x) / 2;
+        if y >= x {
+            return x;
+        }
+        x = y;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lcm() {
+        assert_eq!(least_common_multiple(15, 20), 60);
+        assert_eq!(least_common_multiple(7_usize, 13_usize), 91);
+    }
+
+    #[test]
+    fn test_gcd() {
+        for (a, b, expected) in [(12, 18, 6), (18, 12, 6), (17, 5, 1), (0, 9, 9), (9, 0, 9)] {
+            assert_eq!(greatest_common_divisor(a, b), expected, "gcd({a}, {b})");
+        }
+    }
+
+    #[test]
+    fn test_extended_gcd() {
+        for (a, b) in [(240, 46), (46, 240), (-240, 46), (17, 5), (0, 7)] {
+            let (g, x, y) = extended_gcd(a, b);
+
+            assert_eq!(
+                g,
+                greatest_common_divisor(a.abs(), b.abs()),
+                "gcd({a}, {b})"
+            );
+            assert_eq!(a * x + b * y, g, "bezout coefficients for ({a}, {b})");
+        }
+    }
+
+    #[test]
+    fn test_mod_inverse() {
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(6, 9), None);
+    }
+
+    #[test]
+    fn test_chinese_remainder() {
+        assert_eq!(
+            chinese_remainder(&[(2, 3), (3, 5), (2, 7)]),
+            Some((23, 105))
+        );
+
+        // non coprime moduli
+        assert_eq!(chinese_remainder(&[(2, 6), (8, 10)]), Some((8, 30)));
+        assert_eq!(chinese_remainder(&[(1, 4), (2, 6)]), None);
+
+        // 2020 day 13 example, bus ids with offsets
+        let buses = [(0, 7), (1, 13), (4, 59), (6, 31), (7, 19)];
+        let congruences = buses.map(|(offset, id)| (-offset, id));
+        assert_eq!(chinese_remainder(&congruences), Some((1068781, 3162341)));
+    }
+
+    #[test]
+    fn test_mul_mod() {
+        let m = u64::MAX - 58;
+        assert_eq!(mul_mod(m - 1, m - 1, m), 1);
+        assert_eq!(mul_mod(u64::MAX, 2, 10), 0);
+
+        let m = i128::MAX - 158;
+        assert_eq!(mul_mod_i128(m - 1, m - 1, m), 1);
+        assert_eq!(mul_mod_i128(-1, -1, m), 1);
+        assert_eq!(mul_mod_i128(-3, 4, 7), 2);
+    }
+
+    #[test]
+    fn test_mod_pow() {
+        assert_eq!(mod_pow(4, 13, 497), 445);
+        assert_eq!(mod_pow(2, 0, 7), 1);
+        assert_eq!(mod_pow(5, 3, 1), 0);
+        // Fermat's little theorem with a large prime
+        let p = 1_000_000_007;
+        assert_eq!(mod_pow(123_456_789, p - 1, p), 1);
+    }
+
+    #[test]
+    fn test_integer_sqrt() {
+        for (n, expected) in [
+            (0, 0),
+            (1, 1),
+            (3, 1),
+            (4, 2),
+            (15, 3),
+            (16, 4),
+            (1_000_000_000_000, 1_000_000),
+            (u64::MAX, u32::MAX as u64),
+        ] {
+            assert_eq!(integer_sqrt(n), expected, "sqrt({n})");
+        }
+    }
+}
@@ -1,22 +1,59 @@
+use anyhow::anyhow;
 use std::cmp::{max, min};
-use std::ops::Add;
-
-#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
-pub struct Point {
-    pub x: usize,
-    pub y: usize,
+use std::fmt::Display;
+use std::ops::{Add, Mul, Neg, Sub};
+use std::str::FromStr;
+
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone, Default)]
+pub struct Point<T = usize> {
+    pub x: T,
+    pub y: T,
 }
 
-impl Point {
-    pub fn new(x: usize, y: usize) -> Self {
+impl<T> Point<T> {
+    pub fn new(x: T, y: T) -> Self {
         Self { x, y }
     }
+}
 
-    pub fn manhattan_distance(&self, other: Point) -> usize {
+#[allow(dead_code)]
+impl<T> Point<T>
+where
+    T: Ord + Copy + Add<Output = T> + Sub<Output = T>,
+{
+    pub fn manhattan_distance(&self, other: Self) -> T {
         (max(self.x, other.x) - min(self.x, other.x))
             + (max(self.y, other.y) - min(self.y, other.y))
     }
 
+    pub fn chebyshev_distance(&self, other: Self) -> T {
+        max(
+            max(self.x, other.x) - min(self.x, other.x),
+            max(self.y, other.y) - min(self.y, other.y),
+        )
+    }
+}
+
+// Rotations use screen coordinates, x grows to the right and y grows downwards.
+#[allow(dead_code)]
+impl<T> Point<T>
+where
+    T: Copy + Neg<Output = T>,
+{
+    pub fn rotate_clockwise_90(self) -> Self {
+        Self::new(-self.y, self.x)
+    }
+
+    pub fn rotate_counter_clockwise_90(self) -> Self {
+        Self::new(self.y, -self.x)
+    }
+
+    pub fn rotate_180(self) -> Self {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+impl Point {
     pub fn get_adjacent(self, direction: GridDirection) -> Option<Self> {
         Some(match direction {
             GridDirection::Up => {
@@ -134,23 +171,169 @@ impl Add<GridDirection> for Point {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
-pub struct Point3 {
-    pub x: usize,
-    pub y: usize,
-    pub z: usize,
+impl<T: Add<Output = T>> Add for Point<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.x + other.x, self.y + other.y)
+    }
 }
 
-impl Point3 {
-    pub fn new(x: usize, y: usize, z: usize) -> Self {
+impl<T: Sub<Output = T>> Sub for Point<T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl<T: Mul<Output = T> + Copy> Mul<T> for Point<T> {
+    type Output = Self;
+
+    fn mul(self, scalar: T) -> Self {
+        Self::new(self.x * scalar, self.y * scalar)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone, Default)]
+pub struct Point3<T = i64> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T> Point3<T> {
+    pub fn new(x: T, y: T, z: T) -> Self {
         Self { x, y, z }
     }
+}
+
+#[allow(dead_code)]
+impl<T> Point3<T>
+where
+    T: Ord + Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    // Exact, so points can be ordered by distance without going through floats.
+    pub fn squared_distance(&self, other: Self) -> T {
+        let (dx, dy, dz) = self.abs_diff(other);
+
+        dx * dx + dy * dy + dz * dz
+    }
+
+    pub fn manhattan_distance(&self, other: Self) -> T {
+        let (dx, dy, dz) = self.abs_diff(other);
+
+        dx + dy + dz
+    }
+
+    pub fn chebyshev_distance(&self, other: Self) -> T {
+        let (dx, dy, dz) = self.abs_diff(other);
+
+        max(max(dx, dy), dz)
+    }
+
+    fn abs_diff(&self, other: Self) -> (T, T, T) {
+        (
+            max(self.x, other.x) - min(self.x, other.x),
+            max(self.y, other.y) - min(self.y, other.y),
+            max(self.z, other.z) - min(self.z, other.z),
+        )
+    }
+}
+
+#[allow(dead_code)]
+impl<T> Point3<T>
+where
+    T: Copy + Add<Output = T> + From<i8>,
+{
+    // The points sharing a face with this one.
+    pub fn neighbours6(self) -> impl Iterator<Item = Self> {
+        [
+            (-1, 0, 0),
+            (1, 0, 0),
+            (0, -1, 0),
+            (0, 1, 0),
+            (0, 0, -1),
+            (0, 0, 1),
+        ]
+        .into_iter()
+        .map(move |(dx, dy, dz)| self + Self::new(dx.into(), dy.into(), dz.into()))
+    }
+
+    // The points sharing a face, edge or corner with this one.
+    pub fn neighbours26(self) -> impl Iterator<Item = Self> {
+        (-1..=1)
+            .flat_map(|dx| (-1..=1).flat_map(move |dy| (-1..=1).map(move |dz| (dx, dy, dz))))
+            .filter(|delta| *delta != (0, 0, 0))
+            .map(move |(dx, dy, dz)| self + Self::new(dx.into(), dy.into(), dz.into()))
+    }
+}
+
+impl<T> FromStr for Point3<T>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    type Err = anyhow::Error;
+
+    // Parses `x,y,z`, surrounding whitespace on each coordinate is ignored.
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let mut coordinates = s.split(',').map(|n| {
+            n.trim()
+                .parse::<T>()
+                .map_err(|e| anyhow!("could not parse `{}` in `{s}`: {e}", n.trim()))
+        });
+
+        let (Some(x), Some(y), Some(z), None) = (
+            coordinates.next(),
+            coordinates.next(),
+            coordinates.next(),
+            coordinates.next(),
+        ) else {
+            return Err(anyhow!("expected three coordinates in `{s}`"));
+        };
+
+        Ok(Self::new(x?, y?, z?))
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point3<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point3<T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+impl<T: Mul<Output = T> + Copy> Mul<T> for Point3<T> {
+    type Output = Self;
+
+    fn mul(self, scalar: T) -> Self {
+        Self::new(self.x * scalar, self.y * scalar, self.z * scalar)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point3<T> {
+    type Output = Self;
 
-    pub fn euclidean_distance(&self, other: Self) -> f32 {
-        ((self.x as f32 - other.x as f32).powf(2.0)
-            + (self.y as f32 - other.y as f32).powf(2.0)
-            + (self.z as f32 - other.z as f32).powf(2.0))
-        .sqrt()
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y, -self.z)
     }
 }
 
@@ -167,6 +350,7 @@ pub enum GridDirection {
     DownRight,
 }
 
+#[allow(dead_code)]
 impl GridDirection {
     pub fn all() -> [Self; 8] {
         [
@@ -181,7 +365,15 @@ impl GridDirection {
         ]
     }
 
-    pub fn turn_clockwise_90(&mut self) -> Self {
+    pub fn cardinal() -> [Self; 4] {
+        [Self::Up, Self::Right, Self::Down, Self::Left]
+    }
+
+    pub fn diagonal() -> [Self; 4] {
+        [Self::UpLeft, Self::UpRight, Self::DownRight, Self::DownLeft]
+    }
+
+    pub fn turn_clockwise_90(self) -> Self {
         match self {
             Self::Up => Self::Right,
             Self::Right => Self::Down,
@@ -193,11 +385,67 @@ impl GridDirection {
             Self::DownLeft => Self::UpLeft,
         }
     }
+
+    pub fn turn_counter_clockwise_90(self) -> Self {
+        match self {
+            Self::Up => Self::Left,
+            Self::Left => Self::Down,
+            Self::Down => Self::Right,
+            Self::Right => Self::Up,
+            Self::UpLeft => Self::DownLeft,
+            Self::DownLeft => Self::DownRight,
+            Self::DownRight => Self::UpRight,
+            Self::UpRight => Self::UpLeft,
+        }
+    }
+
+    pub fn opposite(self) -> Self {
+        match self {
+            Self::Up => Self::Down,
+            Self::Down => Self::Up,
+            Self::Left => Self::Right,
+            Self::Right => Self::Left,
+            Self::UpLeft => Self::DownRight,
+            Self::DownRight => Self::UpLeft,
+            Self::UpRight => Self::DownLeft,
+            Self::DownLeft => Self::UpRight,
+        }
+    }
+
+    pub fn delta<T: From<i8>>(self) -> Point<T> {
+        let (x, y) = match self {
+            Self::Up => (0, -1),
+            Self::Down => (0, 1),
+            Self::Left => (-1, 0),
+            Self::Right => (1, 0),
+            Self::UpLeft => (-1, -1),
+            Self::UpRight => (1, -1),
+            Self::DownLeft => (-1, 1),
+            Self::DownRight => (1, 1),
+        };
+
+        Point::new(T::from(x), T::from(y))
+    }
+}
+
+impl TryFrom<char> for GridDirection {
+    type Error = String;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            '^' | 'U' | 'N' => Ok(Self::Up),
+            'v' | 'D' | 'S' => Ok(Self::Down),
+            '<' | 'L' | 'W' => Ok(Self::Left),
+            '>' | 'R' | 'E' => Ok(Self::Right),
+            _ => Err(format!("Could not parse `{value}` as GridDirection")),
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::HashSet;
 
     #[test]
     fn test_point_manhattan_distance() {
@@ -244,4 +492,132 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_signed_point_distances() {
+        let a = Point::new(-3_i64, 4);
+        let b = Point::new(2_i64, -1);
+
+        assert_eq!(a.manhattan_distance(b), 10);
+        assert_eq!(b.manhattan_distance(a), 10);
+        assert_eq!(a.chebyshev_distance(b), 5);
+        assert_eq!(Point::new(1, 7).chebyshev_distance(Point::new(4, 5)), 3);
+    }
+
+    #[test]
+    fn test_point_arithmetic() {
+        let a = Point::new(3_i32, -2);
+        let b = Point::new(-1_i32, 5);
+
+        assert_eq!(a + b, Point::new(2, 3));
+        assert_eq!(a - b, Point::new(4, -7));
+        assert_eq!(a * 3, Point::new(9, -6));
+        assert_eq!(-a, Point::new(-3, 2));
+    }
+
+    #[test]
+    fn test_point_rotation() {
+        let up = GridDirection::Up.delta::<i32>();
+
+        assert_eq!(up.rotate_clockwise_90(), GridDirection::Right.delta());
+        assert_eq!(
+            up.rotate_counter_clockwise_90(),
+            GridDirection::Left.delta()
+        );
+        assert_eq!(up.rotate_180(), GridDirection::Down.delta());
+
+        let p = Point::new(5_i64, 2);
+        assert_eq!(
+            p.rotate_clockwise_90().rotate_clockwise_90(),
+            p.rotate_180()
+        );
+        assert_eq!(p.rotate_clockwise_90().rotate_counter_clockwise_90(), p);
+    }
+
+    #[test]
+    fn test_grid_direction_turns() {
+        for direction in GridDirection::all() {
+            let delta = direction.delta::<i32>();
+
+            assert_eq!(direction.opposite().delta(), -delta);
+            assert_eq!(
+                direction.turn_clockwise_90().delta(),
+                delta.rotate_clockwise_90()
+            );
+            assert_eq!(
+                direction.turn_counter_clockwise_90().delta(),
+                delta.rotate_counter_clockwise_90()
+            );
+            assert_eq!(direction.opposite().opposite(), direction);
+        }
+
+        assert!(
+            GridDirection::cardinal()
+                .iter()
+                .all(|d| d.delta::<i32>().manhattan_distance(Point::default()) == 1)
+        );
+        assert!(
+            GridDirection::diagonal()
+                .iter()
+                .all(|d| d.delta::<i32>().manhattan_distance(Point::default()) == 2)
+        );
+    }
+
+    #[test]
+    fn test_grid_direction_from_char() {
+        for (chars, expected) in [
+            (['^', 'U', 'N'], GridDirection::Up),
+            (['v', 'D', 'S'], GridDirection::Down),
+            (['<', 'L', 'W'], GridDirection::Left),
+            (['>', 'R', 'E'], GridDirection::Right),
+        ] {
+            for ch in chars {
+                assert_eq!(GridDirection::try_from(ch), Ok(expected));
+            }
+        }
+
+        assert!(GridDirection::try_from('x').is_err());
+    }
+
+    #[test]
+    fn test_point3_distances() {
+        let a = Point3::new(162, 817, 812);
+        let b = Point3::new(425, 690, 689);
+
+        assert_eq!(a.squared_distance(b), 263 * 263 + 127 * 127 + 123 * 123);
+        assert_eq!(a.manhattan_distance(b), 263 + 127 + 123);
+        assert_eq!(a.chebyshev_distance(b), 263);
+
+        let a = Point3::new(-1, 2, -3);
+        assert_eq!(a.squared_distance(-a), 4 + 16 + 36);
+        assert_eq!(a + Point3::new(1, 1, 1) * 2, Point3::new(1, 4, -1));
+        assert_eq!(a - a, Point3::default());
+    }
+
+    #[test]
+    fn test_point3_neighbours() {
+        let origin = Point3::<i64>::default();
+
+        let faces = origin.neighbours6().collect::<Vec<_>>();
+        assert_eq!(faces.len(), 6);
+        assert!(faces.iter().all(|p| p.manhattan_distance(origin) == 1));
+
+        let all = origin.neighbours26().collect::<HashSet<_>>();
+        assert_eq!(all.len(), 26);
+        assert!(all.iter().all(|p| p.chebyshev_distance(origin) == 1));
+        assert!(faces.iter().all(|p| all.contains(p)));
+    }
+
+    #[test]
+    fn test_point3_parse() {
+        assert_eq!("1, -2,3".parse::<Point3>().unwrap(), Point3::new(1, -2, 3));
+        assert_eq!(
+            "57,618,57".parse::<Point3<usize>>().unwrap(),
+            Point3::new(57, 618, 57)
+        );
+
+        for input in ["1,2", "1,2,3,4", "1,x,3", "-1,2,3"] {
+            assert!(input.parse::<Point3<u32>>().is_err(), "{input}");
+        }
+    }
 }
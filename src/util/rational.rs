@@ -0,0 +1,313 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, Sub, SubAssign};
+
+use super::number_theory::greatest_common_divisor;
+
+// The signed primitive integers, anything a Rational can be built from.
+pub trait Integer:
+    Copy
+    + Ord
+    + Default
+    + From<i8>
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+    + Neg<Output = Self>
+{
+}
+
+impl<T> Integer for T where
+    T: Copy
+        + Ord
+        + Default
+        + From<i8>
+        + Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + Div<Output = T>
+        + Rem<Output = T>
+        + Neg<Output = T>
+{
+}
+
+// An exact fraction, always kept in lowest terms with a positive denominator so that equal
+// values have equal representations. Intermediate products are not checked for overflow,
+// which is why the default is i128.
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub struct Rational<T = i128> {
+    numer: T,
+    denom: T,
+}
+
+#[allow(dead_code)]
+impl<T> Rational<T>
+where
+    T: Integer,
+{
+    pub fn new(numer: T, denom: T) -> Self {
+        let zero = T::default();
+        assert!(denom != zero, "Rational with a zero denominator");
+
+        let (numer, denom) = if denom < zero {
+            (-numer, -denom)
+        } else {
+            (numer, denom)
+        };
+
+        let divisor = greatest_common_divisor(Self::abs_int(numer), denom);
+
+        Self {
+            numer: numer / divisor,
+            denom: denom / divisor,
+        }
+    }
+
+    pub fn from_integer(value: T) -> Self {
+        Self {
+            numer: value,
+            denom: T::from(1),
+        }
+    }
+
+    pub fn zero() -> Self {
+        Self::from_integer(T::default())
+    }
+
+    pub fn one() -> Self {
+        Self::from_integer(T::from(1))
+    }
+
+    pub fn numer(&self) -> T {
+        self.numer
+    }
+
+    pub fn denom(&self) -> T {
+        self.denom
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.denom == T::from(1)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.numer == T::default()
+    }
+
+    // The integer value if there is no fractional part.
+    pub fn to_integer(self) -> Option<T> {
+        self.is_integer().then_some(self.numer)
+    }
+
+    pub fn floor(&self) -> T {
+        let quotient = self.numer / self.denom;
+
+        if self.numer % self.denom < T::default() {
+            quotient - T::from(1)
+        } else {
+            quotient
+        }
+    }
+
+    pub fn ceil(&self) -> T {
+        -(-*self).floor()
+    }
+
+    pub fn abs(&self) -> Self {
+        Self {
+            numer: Self::abs_int(self.numer),
+            denom: self.denom,
+        }
+    }
+
+    pub fn recip(&self) -> Self {
+        Self::new(self.denom, self.numer)
+    }
+
+    fn abs_int(value: T) -> T {
+        if value < T::default() { -value } else { value }
+    }
+}
+
+impl<T> Default for Rational<T>
+where
+    T: Integer,
+{
+    fn default() -> Self {
+        Self::zero()
+    }
+}
+
+impl<T> From<T> for Rational<T>
+where
+    T: Integer,
+{
+    fn from(value: T) -> Self {
+        Self::from_integer(value)
+    }
+}
+
+macro_rules! impl_rational_op {
+    ($op:ident, $fn:ident, $assign_op:ident, $assign_fn:ident, |$a:ident, $b:ident| $body:expr) => {
+        impl<T> $op for Rational<T>
+        where
+            T: Integer,
+        {
+            type Output = Self;
+
+            fn $fn(self, other: Self) -> Self {
+                let ($a, $b) = (self, other);
+                $body
+            }
+        }
+
+        impl<T> $assign_op for Rational<T>
+        where
+            T: Integer,
+        {
+            fn $assign_fn(&mut self, other: Self) {
+                *self = self.$fn(other);
+            }
+        }
+    };
+}
+
+impl_rational_op!(Add, add, AddAssign, add_assign, |a, b| Rational::new(
+    a.numer * b.denom + b.numer * a.denom,
+    a.denom * b.denom
+));
+impl_rational_op!(Sub, sub, SubAssign, sub_assign, |a, b| Rational::new(
+    a.numer * b.denom - b.numer * a.denom,
+    a.denom * b.denom
+));
+impl_rational_op!(Mul, mul, MulAssign, mul_assign, |a, b| Rational::new(
+    a.numer * b.numer,
+    a.denom * b.denom
+));
+impl_rational_op!(Div, div, DivAssign, div_assign, |a, b| Rational::new(
+    a.numer * b.denom,
+    a.denom * b.numer
+));
+
+impl<T: Neg<Output = T>> Neg for Rational<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self {
+            numer: -self.numer,
+            denom: self.denom,
+        }
+    }
+}
+
+// Denominators are always positive, so cross multiplying keeps the order.
+impl<T> Ord for Rational<T>
+where
+    T: Copy + Ord + Mul<Output = T>,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.numer * other.denom).cmp(&(other.numer * self.denom))
+    }
+}
+
+impl<T> PartialOrd for Rational<T>
+where
+    T: Copy + Ord + Mul<Output = T>,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> fmt::Display for Rational<T>
+where
+    T: fmt::Display + PartialEq + From<i8>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.denom == T::from(1) {
+            write!(f, "{}", self.numer)
+        } else {
+            write!(f, "{}/{}", self.numer, self.denom)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalise() {
+        let half = Rational::new(2, 4);
+        assert_eq!((half.numer(), half.denom()), (1, 2));
+        assert_eq!(Rational::new(3, -6), Rational::new(-1, 2));
+        assert_eq!(Rational::new(0, -5), Rational::zero());
+        assert_eq!(Rational::new(10, 5).to_integer(), Some(2));
+        assert_eq!(Rational::new(10, 4).to_integer(), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_zero_denominator() {
+        Rational::new(1, 0);
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let a = Rational::new(1, 3);
+        let b = Rational::new(1, 6);
+
+        assert_eq!(a + b, Rational::new(1, 2));
+        assert_eq!(a - b, b);
+        assert_eq!(a * b, Rational::new(1, 18));
+        assert_eq!(a / b, Rational::from(2));
+        assert_eq!(-a, Rational::new(-1, 3));
+        assert_eq!(b.recip(), Rational::from(6));
+
+        let mut c = Rational::one();
+        c -= a;
+        c *= Rational::from(3);
+        c /= Rational::new(4, 1);
+        c += Rational::new(1, 2);
+        assert_eq!(c, Rational::one());
+
+        let small = Rational::<i64>::new(3, 4);
+        assert_eq!(small + small, Rational::new(3, 2));
+    }
+
+    #[test]
+    fn test_ordering_and_rounding() {
+        let mut values = [
+            Rational::new(1, 2),
+            Rational::new(-7, 3),
+            Rational::from(2),
+            Rational::new(-1, 3),
+        ];
+        values.sort();
+
+        assert_eq!(
+            values,
+            [
+                Rational::new(-7, 3),
+                Rational::new(-1, 3),
+                Rational::new(1, 2),
+                Rational::from(2)
+            ]
+        );
+
+        for (value, floor, ceil) in [
+            (Rational::new(7, 2), 3, 4),
+            (Rational::new(-7, 2), -4, -3),
+            (Rational::from(5), 5, 5),
+            (Rational::new(-1, 3), -1, 0),
+        ] {
+            assert_eq!(value.floor(), floor, "floor({value})");
+            assert_eq!(value.ceil(), ceil, "ceil({value})");
+        }
+
+        assert_eq!(Rational::new(-7, 2).to_string(), "-7/2");
+        assert_eq!(Rational::new(-7, 2).abs().to_string(), "7/2");
+    }
+}
@@ -0,0 +1,232 @@
+use std::collections::BTreeSet;
+use std::fmt::{self, Display};
+
+use super::{Flow, InstructionSet};
+
+// What a listing needs to know about an instruction beyond how to execute it.
+pub trait Disassemble: InstructionSet {
+    // The ways the instruction can send the program counter somewhere other than the next
+    // instruction, a conditional jump lists where it goes when taken.
+    fn jumps(&self) -> Vec<Flow> {
+        vec![]
+    }
+
+    // False for an unconditional jump or halt, the next instruction is never reached from it.
+    fn falls_through(&self) -> bool {
+        true
+    }
+
+    // What the instruction does as a line of pseudo code, like `b = a % 8`.
+    fn describe(&self) -> String;
+
+    fn mnemonic(&self) -> String {
+        format!("{self:?}")
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Line {
+    pub pc: usize,
+    pub mnemonic: String,
+    pub description: String,
+    // None for a jump that leaves the program, which halts it
+    pub targets: Vec<Option<usize>>,
+    pub falls_through: bool,
+}
+
+// A backwards jump from `end` to `start`, the instructions between them repeat.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct Loop {
+    pub start: usize,
+    pub end: usize,
+}
+
+// An annotated listing of a program, with labels on jump targets and its loops picked out.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Listing {
+    pub lines: Vec<Line>,
+    pub loops: Vec<Loop>,
+    // instructions no path from the start reaches
+    pub unreachable: Vec<usize>,
+}
+
+#[allow(dead_code)]
+impl Listing {
+    pub fn new<I: Disassemble>(program: &[I]) -> Self {
+        let lines = program
+            .iter()
+            .enumerate()
+            .map(|(pc, instruction)| {
+                let targets = instruction
+                    .jumps()
+                    .into_iter()
+                    .map(|flow| match flow {
+                        Flow::Next => pc.checked_add(1),
+                        Flow::Jump(offset) => pc.checked_add_signed(offset),
+                        Flow::Goto(target) => Some(target),
+                        Flow::Halt => None,
+                    })
+                    .map(|target| target.filter(|target| *target < program.len()))
+                    .collect();
+
+                Line {
+                    pc,
+                    mnemonic: instruction.mnemonic(),
+                    description: instruction.describe(),
+                    targets,
+                    falls_through: instruction.falls_through(),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let loops = lines
+            .iter()
+            .flat_map(|line| {
+                line.targets
+                    .iter()
+                    .flatten()
+                    .filter(|target| **target <= line.pc)
+                    .map(|target| Loop {
+                        start: *target,
+                        end: line.pc,
+                    })
+            })
+            .collect();
+
+        let mut reached = vec![false; lines.len()];
+        let mut pending = vec![0];
+        while let Some(pc) = pending.pop() {
+            if pc >= lines.len() || reached[pc] {
+                continue;
+            }
+            reached[pc] = true;
+
+            if lines[pc].falls_through {
+                pending.push(pc + 1);
+            }
+            pending.extend(lines[pc].targets.iter().flatten());
+        }
+        let unreachable = (0..lines.len()).filter(|pc| !reached[*pc]).collect();
+
+        Self {
+            lines,
+            loops,
+            unreachable,
+        }
+    }
+
+    // The addresses that get jumped to, in order.
+    pub fn labels(&self) -> Vec<usize> {
+        self.lines
+            .iter()
+            .flat_map(|line| line.targets.iter().flatten().copied())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    }
+
+    pub fn label(&self, pc: usize) -> Option<String> {
+        self.labels()
+            .iter()
+            .position(|label| *label == pc)
+            .map(|index| format!("L{index}"))
+    }
+}
+
+impl Display for Listing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self
+            .lines
+            .iter()
+            .map(|line| line.mnemonic.len())
+            .max()
+            .unwrap_or_default();
+
+        for line in self.lines.iter() {
+            if let Some(label) = self.label(line.pc) {
+                writeln!(f, "{label}:")?;
+            }
+
+            let mut notes = line
+                .targets
+                .iter()
+                .map(
+                    |target| match target.and_then(|target| self.label(target)) {
+                        Some(label) => format!("-> {label}"),
+                        None => "-> exit".to_string(),
+                    },
+                )
+                .collect::<Vec<_>>();
+            if self.loops.iter().any(|l| l.end == line.pc) {
+                notes.push("loop".to_string());
+            }
+            if self.unreachable.contains(&line.pc) {
+                notes.push("unreachable".to_string());
+            }
+
+            let code = format!(
+                "{:>4}  {:<width$}  {}",
+                line.pc, line.mnemonic, line.description
+            );
+            if notes.is_empty() {
+                writeln!(f, "{code}")?;
+            } else {
+                writeln!(f, "{code:<48} ; {}", notes.join(", "))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::Machine;
+    use super::super::tests::{PROGRAM, Toy};
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    impl Disassemble for Toy {
+        fn jumps(&self) -> Vec<Flow> {
+            match self {
+                Self::Jnz(_, offset) => vec![Flow::Jump(*offset)],
+                _ => vec![],
+            }
+        }
+
+        fn describe(&self) -> String {
+            let name = |r: &usize| (b'a' + *r as u8) as char;
+
+            match self {
+                Self::Set(r, value) => format!("{} = {value}", name(r)),
+                Self::Dec(r) => format!("{0} = {0} - 1", name(r)),
+                Self::Out(r) => format!("out {}", name(r)),
+                Self::Jnz(r, offset) => format!("if {} != 0 jump {offset:+}", name(r)),
+            }
+        }
+    }
+
+    #[test]
+    fn test_listing() {
+        let machine = Machine::<Toy>::parse(&PROGRAM).unwrap();
+        let listing = Listing::new(machine.program());
+
+        assert_eq!(listing.loops, [Loop { start: 1, end: 3 }]);
+        assert_eq!(listing.labels(), [1]);
+        assert_eq!(listing.label(1), Some("L0".to_string()));
+        assert_eq!(listing.label(0), None);
+        assert!(listing.unreachable.is_empty());
+        assert_eq!(listing.lines[3].targets, [Some(1)]);
+
+        #[rustfmt::skip]
+        let expected = [
+            "   0  Set(0, 3)   a = 3",
+            "L0:",
+            "   1  Out(0)      out a",
+            "   2  Dec(0)      a = a - 1",
+            "   3  Jnz(0, -2)  if a != 0 jump -2              ; -> L0, loop",
+            "   4  Out(1)      out b",
+        ];
+        assert_eq!(listing.to_string().lines().collect::<Vec<_>>(), expected);
+    }
+}
@@ -0,0 +1,368 @@
+use anyhow::{Result, anyhow};
+use std::collections::HashSet;
+use std::fmt::{self, Debug, Display};
+use std::str::FromStr;
+
+pub mod disassemble;
+pub mod symbolic;
+
+// The opcodes of one puzzle's machine. The machine itself owns the registers, program counter
+// and cycle count, an instruction set only says what each instruction does to them.
+pub trait InstructionSet: Clone + Debug {
+    type Word: Copy + Default + Debug;
+
+    // The size of the register file, every register starts at the default word.
+    const REGISTERS: usize;
+
+    // How many cycles the instruction takes, its effects land at the end of the last one.
+    fn cycles(&self) -> usize {
+        1
+    }
+
+    fn execute(&self, registers: &mut [Self::Word], output: &mut Vec<Self::Word>) -> Result<Flow>;
+}
+
+// Where the program counter goes after an instruction.
+#[allow(dead_code)]
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum Flow {
+    Next,
+    // relative to the instruction itself
+    Jump(isize),
+    Goto(usize),
+    Halt,
+}
+
+// Why a run stopped.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum Stop {
+    Halted,
+    // about to execute the instruction at this address
+    Breakpoint(usize),
+    CycleLimit,
+}
+
+// The state of the machine just before it executed an instruction.
+#[derive(Debug, Clone)]
+pub struct Trace<I: InstructionSet> {
+    pub cycle: usize,
+    pub pc: usize,
+    pub instruction: I,
+    pub registers: Vec<I::Word>,
+}
+
+impl<I: InstructionSet> Display for Trace<I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:>6} {:>4}: {:<24} {:?}",
+            self.cycle,
+            self.pc,
+            format!("{:?}", self.instruction),
+            self.registers
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Machine<I: InstructionSet> {
+    pub registers: Vec<I::Word>,
+    pub pc: usize,
+    // cycles completed so far
+    pub cycle: usize,
+    pub output: Vec<I::Word>,
+    program: Vec<I>,
+    halted: bool,
+    breakpoints: HashSet<usize>,
+    cycle_limit: Option<usize>,
+    trace: Option<Vec<Trace<I>>>,
+}
+
+#[allow(dead_code)]
+impl<I: InstructionSet> Machine<I> {
+    pub fn new(program: Vec<I>) -> Self {
+        Self {
+            registers: vec![I::Word::default(); I::REGISTERS],
+            pc: 0,
+            cycle: 0,
+            output: vec![],
+            program,
+            halted: false,
+            breakpoints: HashSet::new(),
+            cycle_limit: None,
+            trace: None,
+        }
+    }
+
+    // One instruction per line, blank lines are skipped.
+    pub fn parse(lines: &[&str]) -> Result<Self>
+    where
+        I: FromStr,
+        I::Err: Display,
+    {
+        let program = lines
+            .iter()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(number, line)| {
+                line.trim()
+                    .parse::<I>()
+                    .map_err(|e| anyhow!("Machine::parse: line {} `{line}`: {e}", number + 1))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self::new(program))
+    }
+
+    // Sets the first registers, the rest keep their current value.
+    pub fn with_registers(mut self, values: &[I::Word]) -> Self {
+        self.registers[..values.len()].copy_from_slice(values);
+        self
+    }
+
+    pub fn with_breakpoint(mut self, pc: usize) -> Self {
+        self.breakpoints.insert(pc);
+        self
+    }
+
+    // Stops a run once this many cycles have gone by, for programs that might never halt.
+    pub fn with_cycle_limit(mut self, cycles: usize) -> Self {
+        self.cycle_limit = Some(cycles);
+        self
+    }
+
+    // Records the state before every instruction, see `trace`.
+    pub fn with_trace(mut self) -> Self {
+        self.trace = Some(vec![]);
+        self
+    }
+
+    pub fn program(&self) -> &[I] {
+        &self.program
+    }
+
+    pub fn trace(&self) -> &[Trace<I>] {
+        self.trace.as_deref().unwrap_or_default()
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.halted || self.pc >= self.program.len()
+    }
+
+    // Back to the start of the program with fresh registers, keeping breakpoints and limits.
+    pub fn reset(&mut self, registers: &[I::Word]) {
+        self.registers = vec![I::Word::default(); I::REGISTERS];
+        self.registers[..registers.len()].copy_from_slice(registers);
+        self.pc = 0;
+        self.cycle = 0;
+        self.output.clear();
+        self.halted = false;
+        if let Some(trace) = self.trace.as_mut() {
+            trace.clear();
+        }
+    }
+
+    // Executes one instruction, false if the machine had already halted. Jumping to before
+    // the start or past the end of the program halts it.
+    pub fn step(&mut self) -> Result<bool> {
+        if self.is_halted() {
+            self.halted = true;
+            return Ok(false);
+        }
+
+        let instruction = &self.program[self.pc];
+        if let Some(trace) = self.trace.as_mut() {
+            trace.push(Trace {
+                cycle: self.cycle,
+                pc: self.pc,
+                instruction: instruction.clone(),
+                registers: self.registers.clone(),
+            });
+        }
+
+        let flow = instruction
+            .execute(&mut self.registers, &mut self.output)
+            .map_err(|e| anyhow!("Machine::step: at {} {instruction:?}: {e}", self.pc))?;
+        self.cycle += instruction.cycles();
+
+        match flow {
+            Flow::Next => self.pc += 1,
+            Flow::Jump(offset) => match self.pc.checked_add_signed(offset) {
+                Some(pc) => self.pc = pc,
+                None => self.halted = true,
+            },
+            Flow::Goto(pc) => self.pc = pc,
+            Flow::Halt => self.halted = true,
+        }
+
+        Ok(true)
+    }
+
+    pub fn run(&mut self) -> Result<Stop> {
+        self.run_observing(|_, _| {})
+    }
+
+    // Runs until the machine halts, reaches a breakpoint or hits the cycle limit. `during`
+    // sees every cycle by number, counting from 1, along with the registers as they are while
+    // it is going on. A breakpoint at the address the run starts from is stepped over, so
+    // calling this again resumes.
+    pub fn run_observing<F>(&mut self, mut during: F) -> Result<Stop>
+    where
+        F: FnMut(usize, &[I::Word]),
+    {
+        let start = self.pc;
+        let mut first = true;
+
+        loop {
+            if self.is_halted() {
+                self.halted = true;
+                return Ok(Stop::Halted);
+            }
+            if self.cycle_limit.is_some_and(|limit| self.cycle >= limit) {
+                return Ok(Stop::CycleLimit);
+            }
+            if self.breakpoints.contains(&self.pc) && !(first && self.pc == start) {
+                return Ok(Stop::Breakpoint(self.pc));
+            }
+            first = false;
+
+            for cycle in 1..=self.program[self.pc].cycles() {
+                during(self.cycle + cycle, &self.registers);
+            }
+            self.step()?;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    // A countdown machine, `dec` takes two cycles.
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    pub(super) enum Toy {
+        Set(usize, i64),
+        Dec(usize),
+        Out(usize),
+        Jnz(usize, isize),
+    }
+
+    impl InstructionSet for Toy {
+        type Word = i64;
+        const REGISTERS: usize = 2;
+
+        fn cycles(&self) -> usize {
+            match self {
+                Self::Dec(_) => 2,
+                _ => 1,
+            }
+        }
+
+        fn execute(&self, registers: &mut [i64], output: &mut Vec<i64>) -> Result<Flow> {
+            match self {
+                Self::Set(r, value) => registers[*r] = *value,
+                Self::Dec(r) => registers[*r] -= 1,
+                Self::Out(r) => output.push(registers[*r]),
+                Self::Jnz(r, offset) if registers[*r] != 0 => return Ok(Flow::Jump(*offset)),
+                Self::Jnz(..) => {}
+            }
+
+            Ok(Flow::Next)
+        }
+    }
+
+    impl FromStr for Toy {
+        type Err = anyhow::Error;
+
+        fn from_str(s: &str) -> Result<Self> {
+            let parts = s.split_whitespace().collect::<Vec<_>>();
+            let number = |index: usize| {
+                parts
+                    .get(index)
+                    .ok_or_else(|| anyhow!("missing argument {index}"))?
+                    .parse::<i64>()
+                    .map_err(|e| anyhow!("{e}"))
+            };
+
+            match parts.first() {
+                Some(&"set") => Ok(Self::Set(number(1)? as usize, number(2)?)),
+                Some(&"dec") => Ok(Self::Dec(number(1)? as usize)),
+                Some(&"out") => Ok(Self::Out(number(1)? as usize)),
+                Some(&"jnz") => Ok(Self::Jnz(number(1)? as usize, number(2)? as isize)),
+                _ => Err(anyhow!("unknown instruction")),
+            }
+        }
+    }
+
+    #[rustfmt::skip]
+    pub(super) const PROGRAM: [&str; 5] = [
+        "set 0 3",
+        "out 0",
+        "dec 0",
+        "jnz 0 -2",
+        "out 1",
+    ];
+
+    #[test]
+    fn test_machine_run() {
+        let mut machine = Machine::<Toy>::parse(&PROGRAM)
+            .unwrap()
+            .with_registers(&[0, 7]);
+
+        assert_eq!(machine.run().unwrap(), Stop::Halted);
+        assert_eq!(machine.output, [3, 2, 1, 7]);
+        assert_eq!(machine.registers, [0, 7]);
+        assert_eq!(machine.cycle, 1 + 3 * 4 + 1);
+        assert!(!machine.step().unwrap());
+
+        machine.reset(&[]);
+        machine.run().unwrap();
+        assert_eq!(machine.output, [3, 2, 1, 0]);
+
+        assert!(Machine::<Toy>::parse(&["set 0 1", "inc 0"]).is_err());
+    }
+
+    #[test]
+    fn test_machine_observing() {
+        let mut machine = Machine::<Toy>::parse(&PROGRAM).unwrap();
+
+        let mut seen = vec![];
+        machine
+            .run_observing(|cycle, registers| seen.push((cycle, registers[0])))
+            .unwrap();
+
+        // the register only changes once `dec` has finished both of its cycles
+        assert_eq!(seen[..6], [(1, 0), (2, 3), (3, 3), (4, 3), (5, 2), (6, 2)]);
+        assert_eq!(seen.len(), machine.cycle);
+    }
+
+    #[test]
+    fn test_machine_breakpoints() {
+        let mut machine = Machine::<Toy>::parse(&PROGRAM)
+            .unwrap()
+            .with_breakpoint(2)
+            .with_trace();
+
+        assert_eq!(machine.run().unwrap(), Stop::Breakpoint(2));
+        assert_eq!(machine.output, [3]);
+        assert_eq!(machine.run().unwrap(), Stop::Breakpoint(2));
+        assert_eq!(machine.output, [3, 2]);
+        assert_eq!(machine.registers[0], 2);
+
+        let pcs = machine.trace().iter().map(|t| t.pc).collect::<Vec<_>>();
+        assert_eq!(pcs, [0, 1, 2, 3, 1]);
+        assert_eq!(machine.trace()[3].instruction, Toy::Jnz(0, -2));
+        assert_eq!(machine.trace()[3].registers, [2, 0]);
+        assert_eq!(
+            machine.trace()[3].to_string(),
+            "     4    3: Jnz(0, -2)               [2, 0]"
+        );
+
+        let mut forever = Machine::<Toy>::parse(&["set 0 1", "jnz 0 0"])
+            .unwrap()
+            .with_cycle_limit(100);
+        assert_eq!(forever.run().unwrap(), Stop::CycleLimit);
+        assert_eq!(forever.cycle, 100);
+    }
+}
@@ -0,0 +1,413 @@
+use anyhow::{Result, anyhow};
+use std::collections::BTreeSet;
+use std::fmt::{self, Display};
+use std::ops::{Add, BitXor, Div, Mul, Rem, Shr, Sub};
+
+use super::{Flow, InstructionSet};
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
+pub enum Op {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+    Xor,
+    Shr,
+}
+
+impl Op {
+    fn apply(self, a: i64, b: i64) -> Option<i64> {
+        match self {
+            Self::Add => a.checked_add(b),
+            Self::Sub => a.checked_sub(b),
+            Self::Mul => a.checked_mul(b),
+            Self::Div => a.checked_div(b),
+            Self::Rem => a.checked_rem_euclid(b),
+            Self::Xor => Some(a ^ b),
+            Self::Shr => match b {
+                ..0 => None,
+                0..64 => Some(a >> b),
+                _ => Some(if a < 0 { -1 } else { 0 }),
+            },
+        }
+    }
+
+    // The single constant that does the work of `a` then `b`, for the operations that chain
+    // like `(x + a) + b == x + (a + b)`. None when the two don't combine or it would overflow.
+    fn merge(self, a: i64, b: i64) -> Option<i64> {
+        match self {
+            Self::Add | Self::Sub => a.checked_add(b),
+            Self::Xor => Some(a ^ b),
+            Self::Shr if a >= 0 && b >= 0 => a.checked_add(b),
+            _ => None,
+        }
+    }
+
+    fn commutes(self) -> bool {
+        matches!(self, Self::Add | Self::Mul | Self::Xor)
+    }
+
+    fn symbol(self) -> &'static str {
+        match self {
+            Self::Add => "+",
+            Self::Sub => "-",
+            Self::Mul => "*",
+            Self::Div => "/",
+            Self::Rem => "%",
+            Self::Xor => "^",
+            Self::Shr => ">>",
+        }
+    }
+}
+
+// A value in terms of the registers as they were when symbolic execution started. Registers
+// print as letters, register 0 is `a`.
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub enum Expr {
+    Constant(i64),
+    Register(usize),
+    Binary(Op, Box<Expr>, Box<Expr>),
+}
+
+#[allow(dead_code)]
+impl Expr {
+    // Builds `left op right`, folding constants and dropping operations that do nothing.
+    pub fn binary(op: Op, left: Self, right: Self) -> Self {
+        use Expr::{Binary, Constant};
+
+        let (left, right) = match (&left, &right) {
+            (Constant(_), Constant(_)) => (left, right),
+            (Constant(_), _) if op.commutes() => (right, left),
+            _ => (left, right),
+        };
+
+        match (op, &left, &right) {
+            (_, Constant(a), Constant(b)) if op.apply(*a, *b).is_some() => {
+                Constant(op.apply(*a, *b).unwrap())
+            }
+            (Op::Add | Op::Sub | Op::Xor | Op::Shr, _, Constant(0)) => left,
+            (Op::Mul | Op::Div, _, Constant(1)) => left,
+            (Op::Mul, _, Constant(0)) | (Op::Rem, _, Constant(1)) => Constant(0),
+            (Op::Shr, Constant(0), _) => left,
+            (Op::Sub | Op::Xor, _, _) if left == right => Constant(0),
+            (Op::Rem, Binary(Op::Rem, _, inner), _) if **inner == right => left,
+            (Op::Add | Op::Sub | Op::Xor | Op::Shr, Binary(inner, value, first), Constant(b))
+                if *inner == op && first.constant().and_then(|a| op.merge(a, *b)).is_some() =>
+            {
+                let combined = op.merge(first.constant().unwrap(), *b).unwrap();
+                Self::binary(op, (**value).clone(), Constant(combined))
+            }
+            _ => Binary(op, Box::new(left), Box::new(right)),
+        }
+    }
+
+    pub fn constant(&self) -> Option<i64> {
+        match self {
+            Self::Constant(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    // The value given the starting registers, None if it divides by zero or overflows.
+    pub fn evaluate(&self, registers: &[i64]) -> Option<i64> {
+        match self {
+            Self::Constant(value) => Some(*value),
+            Self::Register(r) => registers.get(*r).copied(),
+            Self::Binary(op, left, right) => {
+                op.apply(left.evaluate(registers)?, right.evaluate(registers)?)
+            }
+        }
+    }
+
+    // The starting registers the value depends on.
+    pub fn registers(&self) -> BTreeSet<usize> {
+        match self {
+            Self::Constant(_) => BTreeSet::new(),
+            Self::Register(r) => BTreeSet::from([*r]),
+            Self::Binary(_, left, right) => &left.registers() | &right.registers(),
+        }
+    }
+}
+
+impl Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let nested = |expr: &Self| match expr {
+            Self::Binary(..) => format!("({expr})"),
+            _ => expr.to_string(),
+        };
+
+        match self {
+            Self::Constant(value) => write!(f, "{value}"),
+            Self::Register(r) => write!(f, "{}", (b'a' + *r as u8) as char),
+            Self::Binary(op, left, right) => {
+                write!(f, "{} {} {}", nested(left), op.symbol(), nested(right))
+            }
+        }
+    }
+}
+
+macro_rules! impl_expr_op {
+    ($trait:ident, $fn:ident, $op:expr) => {
+        impl $trait for Expr {
+            type Output = Self;
+
+            fn $fn(self, other: Self) -> Self {
+                Self::binary($op, self, other)
+            }
+        }
+
+        impl $trait<i64> for Expr {
+            type Output = Self;
+
+            fn $fn(self, other: i64) -> Self {
+                Self::binary($op, self, Self::Constant(other))
+            }
+        }
+    };
+}
+
+impl_expr_op!(Add, add, Op::Add);
+impl_expr_op!(Sub, sub, Op::Sub);
+impl_expr_op!(Mul, mul, Op::Mul);
+impl_expr_op!(Div, div, Op::Div);
+impl_expr_op!(Rem, rem, Op::Rem);
+impl_expr_op!(BitXor, bitxor, Op::Xor);
+impl_expr_op!(Shr, shr, Op::Shr);
+
+// What an instruction does when executed on expressions rather than values.
+#[allow(dead_code)]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum SymbolicFlow {
+    Flow(Flow),
+    // goes to `taken` if `condition` is not zero, otherwise on to the next instruction
+    Branch { condition: Expr, taken: Flow },
+}
+
+pub trait Symbolic: InstructionSet {
+    fn execute_symbolic(
+        &self,
+        registers: &mut [Expr],
+        output: &mut Vec<Expr>,
+    ) -> Result<SymbolicFlow>;
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum SymbolicStop {
+    Halted,
+    // waiting on `take_branch` to say which way the branch at `pc` goes
+    Branch { pc: usize, condition: Expr },
+    StepLimit,
+}
+
+// Runs a program on expressions, so the registers and output end up as formulas of the
+// registers it started with. Branches that depend on those are handed back to the caller.
+#[derive(Debug, Clone)]
+pub struct SymbolicMachine<'a, I: Symbolic> {
+    pub registers: Vec<Expr>,
+    pub pc: usize,
+    pub steps: usize,
+    pub output: Vec<Expr>,
+    program: &'a [I],
+    halted: bool,
+    pending: Option<Flow>,
+}
+
+#[allow(dead_code)]
+impl<'a, I: Symbolic> SymbolicMachine<'a, I> {
+    pub fn new(program: &'a [I]) -> Self {
+        Self {
+            registers: (0..I::REGISTERS).map(Expr::Register).collect(),
+            pc: 0,
+            steps: 0,
+            output: vec![],
+            program,
+            halted: false,
+            pending: None,
+        }
+    }
+
+    // Fixes a register to a known value instead of leaving it unknown.
+    pub fn with_register(mut self, register: usize, value: Expr) -> Self {
+        self.registers[register] = value;
+        self
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.halted || self.pc >= self.program.len()
+    }
+
+    fn apply(&mut self, flow: Flow) {
+        match flow {
+            Flow::Next => self.pc += 1,
+            Flow::Jump(offset) => match self.pc.checked_add_signed(offset) {
+                Some(pc) => self.pc = pc,
+                None => self.halted = true,
+            },
+            Flow::Goto(pc) => self.pc = pc,
+            Flow::Halt => self.halted = true,
+        }
+    }
+
+    // Executes up to `max_steps` instructions, stopping early at a branch it can't decide.
+    // Branches on a constant condition are followed.
+    pub fn run(&mut self, max_steps: usize) -> Result<SymbolicStop> {
+        if self.pending.is_some() {
+            return Err(anyhow!(
+                "SymbolicMachine::run: the branch at {} is undecided",
+                self.pc
+            ));
+        }
+
+        for _ in 0..max_steps {
+            if self.is_halted() {
+                return Ok(SymbolicStop::Halted);
+            }
+
+            let flow = self.program[self.pc]
+                .execute_symbolic(&mut self.registers, &mut self.output)
+                .map_err(|e| anyhow!("SymbolicMachine::run: at {}: {e}", self.pc))?;
+            self.steps += 1;
+
+            match flow {
+                SymbolicFlow::Flow(flow) => self.apply(flow),
+                SymbolicFlow::Branch { condition, taken } => match condition.constant() {
+                    Some(0) => self.apply(Flow::Next),
+                    Some(_) => self.apply(taken),
+                    None => {
+                        self.pending = Some(taken);
+                        return Ok(SymbolicStop::Branch {
+                            pc: self.pc,
+                            condition,
+                        });
+                    }
+                },
+            }
+        }
+
+        match self.is_halted() {
+            true => Ok(SymbolicStop::Halted),
+            false => Ok(SymbolicStop::StepLimit),
+        }
+    }
+
+    // Carries on past the branch `run` stopped at, one way or the other.
+    pub fn take_branch(&mut self, taken: bool) -> Result<()> {
+        let flow = self
+            .pending
+            .take()
+            .ok_or_else(|| anyhow!("SymbolicMachine::take_branch: not waiting on a branch"))?;
+
+        self.apply(if taken { flow } else { Flow::Next });
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::Machine;
+    use super::super::tests::{PROGRAM, Toy};
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_expr_simplify() {
+        let a = Expr::Register(0);
+        let b = Expr::Register(1);
+
+        assert_eq!(Expr::Constant(6) * 7 % 5, Expr::Constant(2));
+        assert_eq!(a.clone() + 0, a);
+        assert_eq!(a.clone() ^ a.clone(), Expr::Constant(0));
+        assert_eq!((a.clone() >> 3) >> 2, a.clone() >> 5);
+        assert_eq!((a.clone() ^ 1) ^ 5, a.clone() ^ 4);
+        assert_eq!((a.clone() - 1) - 2, a.clone() - 3);
+
+        // constants that can't be merged are left as they are
+        let overflowing = (a.clone() + i64::MAX) + 1;
+        assert_eq!(overflowing.to_string(), "(a + 9223372036854775807) + 1");
+        assert_eq!(overflowing.evaluate(&[0]), None);
+        assert_eq!(((a.clone() >> -1) >> 1).to_string(), "(a >> -1) >> 1");
+        assert_eq!(a.clone() % 8 % 8, a.clone() % 8);
+        assert_eq!(Expr::Constant(3) * a.clone(), a.clone() * 3);
+        assert_eq!(Expr::Constant(1) / 0, Expr::Constant(1) / 0);
+
+        let expr = ((a.clone() % 8) ^ 1) ^ (b.clone() >> a.clone());
+        assert_eq!(expr.to_string(), "((a % 8) ^ 1) ^ (b >> a)");
+        assert_eq!(expr.registers(), BTreeSet::from([0, 1]));
+        assert_eq!(
+            expr.evaluate(&[13, 40000]),
+            Some(((13 % 8) ^ 1) ^ (40000 >> 13))
+        );
+        assert_eq!((a / b).evaluate(&[1, 0]), None);
+    }
+
+    impl Symbolic for Toy {
+        fn execute_symbolic(
+            &self,
+            registers: &mut [Expr],
+            output: &mut Vec<Expr>,
+        ) -> Result<SymbolicFlow> {
+            match self {
+                Self::Set(r, value) => registers[*r] = Expr::Constant(*value),
+                Self::Dec(r) => registers[*r] = registers[*r].clone() - 1,
+                Self::Out(r) => output.push(registers[*r].clone()),
+                Self::Jnz(r, offset) => {
+                    return Ok(SymbolicFlow::Branch {
+                        condition: registers[*r].clone(),
+                        taken: Flow::Jump(*offset),
+                    });
+                }
+            }
+
+            Ok(SymbolicFlow::Flow(Flow::Next))
+        }
+    }
+
+    fn program(lines: &[&str]) -> Vec<Toy> {
+        Machine::<Toy>::parse(lines).unwrap().program().to_vec()
+    }
+
+    fn printed(machine: &SymbolicMachine<Toy>) -> Vec<String> {
+        machine.output.iter().map(|o| o.to_string()).collect()
+    }
+
+    #[test]
+    fn test_symbolic_machine() {
+        let straight = program(&["dec 1", "dec 1", "out 1", "out 0"]);
+        let mut machine = SymbolicMachine::new(&straight);
+
+        assert_eq!(machine.run(10).unwrap(), SymbolicStop::Halted);
+        assert_eq!(printed(&machine), ["b - 2", "a"]);
+
+        // the countdown sets its own start, so every branch is decided along the way
+        let countdown = program(&PROGRAM);
+        let mut machine = SymbolicMachine::new(&countdown);
+
+        assert_eq!(machine.run(100).unwrap(), SymbolicStop::Halted);
+        assert_eq!(printed(&machine), ["3", "2", "1", "b"]);
+        assert_eq!(machine.steps, 11);
+
+        let unknown = program(&["out 0", "dec 0", "jnz 0 -2"]);
+        let mut machine = SymbolicMachine::new(&unknown);
+
+        let condition = Expr::Register(0) - 1;
+        assert_eq!(
+            machine.run(10).unwrap(),
+            SymbolicStop::Branch { pc: 2, condition }
+        );
+        assert!(machine.run(10).is_err());
+        machine.take_branch(true).unwrap();
+
+        let condition = Expr::Register(0) - 2;
+        assert_eq!(
+            machine.run(10).unwrap(),
+            SymbolicStop::Branch { pc: 2, condition }
+        );
+        machine.take_branch(false).unwrap();
+
+        assert_eq!(machine.run(10).unwrap(), SymbolicStop::Halted);
+        assert_eq!(printed(&machine), ["a", "a - 1"]);
+        assert!(machine.take_branch(true).is_err());
+    }
+}
@@ -0,0 +1,362 @@
+use anyhow::{Result, anyhow};
+use std::collections::{HashSet, VecDeque};
+use std::ops::{Index, IndexMut};
+
+use super::point::Point3;
+
+// An axis aligned box, both corners are inclusive.
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub struct BoundingBox {
+    pub min: Point3,
+    pub max: Point3,
+}
+
+#[allow(dead_code)]
+impl BoundingBox {
+    pub fn new(min: Point3, max: Point3) -> Self {
+        Self { min, max }
+    }
+
+    pub fn from_points<'a, I: IntoIterator<Item = &'a Point3>>(points: I) -> Option<Self> {
+        let mut points = points.into_iter();
+        let first = *points.next()?;
+
+        Some(points.fold(Self::new(first, first), |bounds, point| {
+            Self::new(
+                Point3::new(
+                    bounds.min.x.min(point.x),
+                    bounds.min.y.min(point.y),
+                    bounds.min.z.min(point.z),
+                ),
+                Point3::new(
+                    bounds.max.x.max(point.x),
+                    bounds.max.y.max(point.y),
+                    bounds.max.z.max(point.z),
+                ),
+            )
+        }))
+    }
+
+    pub fn contains(&self, point: &Point3) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x)
+            && (self.min.y..=self.max.y).contains(&point.y)
+            && (self.min.z..=self.max.z).contains(&point.z)
+    }
+
+    // Grows the box by `by` in every direction.
+    pub fn expand(self, by: i64) -> Self {
+        let delta = Point3::new(by, by, by);
+
+        Self::new(self.min - delta, self.max + delta)
+    }
+
+    // The number of cells along each axis.
+    pub fn dimensions(&self) -> Point3 {
+        self.max - self.min + Point3::new(1, 1, 1)
+    }
+
+    pub fn volume(&self) -> i64 {
+        let dimensions = self.dimensions();
+
+        dimensions.x * dimensions.y * dimensions.z
+    }
+
+    pub fn points(&self) -> impl Iterator<Item = Point3> {
+        let (min, max) = (self.min, self.max);
+
+        (min.z..=max.z).flat_map(move |z| {
+            (min.y..=max.y).flat_map(move |y| (min.x..=max.x).map(move |x| Point3::new(x, y, z)))
+        })
+    }
+}
+
+// A dense 3D grid covering a bounding box, which may start at negative coordinates.
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct Grid3<T> {
+    cells: Vec<T>,
+    bounds: BoundingBox,
+    pub width: usize,
+    pub height: usize,
+    pub depth: usize,
+}
+
+#[allow(dead_code)]
+impl<T> Grid3<T>
+where
+    T: Clone,
+{
+    pub fn new(bounds: BoundingBox, item: T) -> Self {
+        let dimensions = bounds.dimensions();
+        let (width, height, depth) = (
+            dimensions.x.max(0) as usize,
+            dimensions.y.max(0) as usize,
+            dimensions.z.max(0) as usize,
+        );
+
+        Self {
+            cells: vec![item; width * height * depth],
+            bounds,
+            width,
+            height,
+            depth,
+        }
+    }
+
+    pub fn bounds(&self) -> BoundingBox {
+        self.bounds
+    }
+
+    pub fn is_in_bounds(&self, point: &Point3) -> bool {
+        self.bounds.contains(point)
+    }
+
+    pub fn get(&self, point: &Point3) -> Option<&T> {
+        self.point_idx(point).map(|idx| &self.cells[idx])
+    }
+
+    pub fn get_mut(&mut self, point: &Point3) -> Option<&mut T> {
+        self.point_idx(point).map(|idx| &mut self.cells[idx])
+    }
+
+    pub fn set(&mut self, point: &Point3, item: T) -> Result<()> {
+        let bounds = self.bounds;
+        let cell = self
+            .get_mut(point)
+            .ok_or_else(|| anyhow!("{point:?} is outside {bounds:?}"))?;
+        *cell = item;
+
+        Ok(())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Point3, &T)> {
+        self.bounds.points().zip(self.cells.iter())
+    }
+
+    pub fn neighbours6(&self, point: &Point3) -> impl Iterator<Item = (Point3, &T)> {
+        point
+            .neighbours6()
+            .filter_map(|neighbour| self.get(&neighbour).map(|cell| (neighbour, cell)))
+    }
+
+    fn point_idx(&self, point: &Point3) -> Option<usize> {
+        if !self.bounds.contains(point) {
+            return None;
+        }
+
+        let offset = *point - self.bounds.min;
+        let (x, y, z) = (offset.x as usize, offset.y as usize, offset.z as usize);
+
+        Some((z * self.height + y) * self.width + x)
+    }
+}
+
+impl<T: Clone> Index<&Point3> for Grid3<T> {
+    type Output = T;
+
+    fn index(&self, point: &Point3) -> &T {
+        self.get(point)
+            .unwrap_or_else(|| panic!("{point:?} is outside {:?}", self.bounds))
+    }
+}
+
+impl<T: Clone> IndexMut<&Point3> for Grid3<T> {
+    fn index_mut(&mut self, point: &Point3) -> &mut T {
+        let bounds = self.bounds;
+
+        self.get_mut(point)
+            .unwrap_or_else(|| panic!("{point:?} is outside {bounds:?}"))
+    }
+}
+
+// A sparse set of filled unit cubes.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct VoxelSet {
+    voxels: HashSet<Point3>,
+}
+
+#[allow(dead_code)]
+impl VoxelSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, voxel: Point3) -> bool {
+        self.voxels.insert(voxel)
+    }
+
+    pub fn remove(&mut self, voxel: &Point3) -> bool {
+        self.voxels.remove(voxel)
+    }
+
+    pub fn contains(&self, voxel: &Point3) -> bool {
+        self.voxels.contains(voxel)
+    }
+
+    pub fn len(&self) -> usize {
+        self.voxels.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.voxels.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Point3> {
+        self.voxels.iter()
+    }
+
+    pub fn bounding_box(&self) -> Option<BoundingBox> {
+        BoundingBox::from_points(&self.voxels)
+    }
+
+    // Faces that do not touch another voxel, including those facing enclosed pockets.
+    pub fn surface_area(&self) -> usize {
+        self.voxels
+            .iter()
+            .flat_map(|voxel| voxel.neighbours6())
+            .filter(|neighbour| !self.contains(neighbour))
+            .count()
+    }
+
+    // The empty cells reachable from outside the set, limited to the bounding box grown by
+    // one so the fill can wrap around the whole shape.
+    pub fn exterior(&self) -> HashSet<Point3> {
+        let Some(bounds) = self.bounding_box() else {
+            return HashSet::new();
+        };
+        let bounds = bounds.expand(1);
+
+        let mut exterior = HashSet::from([bounds.min]);
+        let mut queue = VecDeque::from([bounds.min]);
+
+        while let Some(current) = queue.pop_front() {
+            for neighbour in current.neighbours6() {
+                if bounds.contains(&neighbour)
+                    && !self.contains(&neighbour)
+                    && exterior.insert(neighbour)
+                {
+                    queue.push_back(neighbour);
+                }
+            }
+        }
+
+        exterior
+    }
+
+    // Faces that can be reached from outside, enclosed pockets are not counted.
+    pub fn exterior_surface_area(&self) -> usize {
+        let exterior = self.exterior();
+
+        self.voxels
+            .iter()
+            .flat_map(|voxel| voxel.neighbours6())
+            .filter(|neighbour| exterior.contains(neighbour))
+            .count()
+    }
+
+    pub fn to_grid3(&self) -> Option<Grid3<bool>> {
+        let mut grid = Grid3::new(self.bounding_box()?, false);
+
+        for voxel in self.voxels.iter() {
+            grid[voxel] = true;
+        }
+
+        Some(grid)
+    }
+}
+
+impl FromIterator<Point3> for VoxelSet {
+    fn from_iter<I: IntoIterator<Item = Point3>>(iter: I) -> Self {
+        Self {
+            voxels: iter.into_iter().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn parse(lines: &[&str]) -> VoxelSet {
+        lines
+            .iter()
+            .map(|line| line.parse::<Point3>().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_bounding_box() {
+        let bounds = BoundingBox::from_points(&[
+            Point3::new(1, -2, 3),
+            Point3::new(-1, 4, 0),
+            Point3::new(0, 0, 0),
+        ])
+        .unwrap();
+
+        assert_eq!(bounds.min, Point3::new(-1, -2, 0));
+        assert_eq!(bounds.max, Point3::new(1, 4, 3));
+        assert_eq!(bounds.dimensions(), Point3::new(3, 7, 4));
+        assert_eq!(bounds.volume(), 84);
+        assert_eq!(bounds.points().count(), 84);
+        assert!(bounds.contains(&Point3::new(-1, 4, 3)));
+        assert!(!bounds.contains(&Point3::new(-2, 0, 0)));
+        assert_eq!(bounds.expand(1).volume(), 5 * 9 * 6);
+
+        assert_eq!(BoundingBox::from_points(&[]), None);
+    }
+
+    #[test]
+    fn test_grid3() {
+        let bounds = BoundingBox::new(Point3::new(-1, -1, -1), Point3::new(1, 1, 1));
+        let mut grid = Grid3::new(bounds, 0);
+
+        assert_eq!((grid.width, grid.height, grid.depth), (3, 3, 3));
+
+        grid[&Point3::new(-1, 0, 1)] = 5;
+        grid.set(&Point3::new(0, 0, 0), 7).unwrap();
+        assert!(grid.set(&Point3::new(2, 0, 0), 1).is_err());
+
+        assert_eq!(grid.get(&Point3::new(-1, 0, 1)), Some(&5));
+        assert_eq!(grid.get(&Point3::new(0, 0, -2)), None);
+        assert_eq!(grid.iter().map(|(_, cell)| cell).sum::<i32>(), 12);
+
+        let corner = grid.neighbours6(&Point3::new(-1, -1, -1)).count();
+        assert_eq!(corner, 3);
+
+        let centre = grid
+            .neighbours6(&Point3::new(-1, 0, 0))
+            .map(|(_, cell)| *cell)
+            .collect::<Vec<_>>();
+        assert_eq!(centre.len(), 5);
+        assert!(centre.contains(&5) && centre.contains(&7));
+    }
+
+    #[test]
+    fn test_surface_area() {
+        let voxels = parse(&["1,1,1", "2,1,1"]);
+        assert_eq!(voxels.surface_area(), 10);
+        assert_eq!(voxels.exterior_surface_area(), 10);
+
+        // 2022 day 18 example
+        #[rustfmt::skip]
+        let voxels = parse(&[
+            "2,2,2", "1,2,2", "3,2,2", "2,1,2", "2,3,2", "2,2,1", "2,2,3",
+            "2,2,4", "2,2,6", "1,2,5", "3,2,5", "2,1,5", "2,3,5",
+        ]);
+
+        assert_eq!(voxels.surface_area(), 64);
+        assert_eq!(voxels.exterior_surface_area(), 58);
+        assert!(!voxels.exterior().contains(&Point3::new(2, 2, 5)));
+    }
+
+    #[test]
+    fn test_to_grid3() {
+        let voxels = parse(&["0,0,0", "2,1,0"]);
+        let grid = voxels.to_grid3().unwrap();
+
+        assert_eq!((grid.width, grid.height, grid.depth), (3, 2, 1));
+        assert_eq!(grid.iter().filter(|(_, cell)| **cell).count(), 2);
+        assert!(grid[&Point3::new(2, 1, 0)]);
+        assert!(VoxelSet::new().to_grid3().is_none());
+    }
+}
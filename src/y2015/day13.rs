@@ -1,4 +1,5 @@
-use std::collections::{HashMap, HashSet};
+use crate::util::combinatorics::permutations;
+use crate::util::graph::Graph;
 
 pub fn solve(input: &[&str]) -> String {
     let happiness_graph = create_happiness_graph(input);
@@ -9,77 +10,50 @@ pub fn solve(input: &[&str]) -> String {
     format!(" Part1: {} \n Part2: {}", part1, part2)
 }
 
-fn create_happiness_graph(input: &[&str]) -> HashMap<String, HashMap<String, i32>> {
-    let mut graph = HashMap::new();
+fn create_happiness_graph(input: &[&str]) -> Graph<(), i32> {
+    let mut graph = Graph::new();
 
     for line in input {
-        let person = line.split(' ').nth(0).unwrap().to_string();
+        let person = line.split(' ').nth(0).unwrap();
         let sign = match line.split(' ').nth(2).unwrap() {
             "gain" => 1,
             "lose" => -1,
             _ => panic!("cant parse sign from line: {}", line),
         };
         let amount = line.split(' ').nth(3).unwrap().parse::<i32>().unwrap();
-        let other_person = line
-            .split(' ')
-            .nth(10)
-            .unwrap()
-            .strip_suffix('.')
-            .unwrap()
-            .to_string();
-
-        (*graph.entry(person).or_insert(HashMap::new())).insert(other_person, amount * sign);
+        let other_person = line.split(' ').nth(10).unwrap().strip_suffix('.').unwrap();
+
+        let (person, other_person) = (graph.node_id(person), graph.node_id(other_person));
+        graph.add_edge(person, other_person, amount * sign);
     }
 
     graph
 }
 
-fn maximize_happiness(happiness_graph: &HashMap<String, HashMap<String, i32>>) -> i32 {
-    maximize_happiness_rec(happiness_graph, &HashSet::new(), None, 0, 0)
-}
-
-fn maximize_happiness_rec(
-    distance_graph: &HashMap<String, HashMap<String, i32>>,
-    visited: &HashSet<String>,
-    current_location: Option<&str>,
-    current_distance: i32,
-    longest_distance: i32,
-) -> i32 {
-    if visited.len() == distance_graph.len() {
-        return current_distance;
+// Seatings are round a table, so the first person can stay put while the rest move round.
+fn maximize_happiness(happiness_graph: &Graph<(), i32>) -> i32 {
+    if happiness_graph.len() < 2 {
+        return 0;
     }
 
-    let mut distance = 0;
-    for node in distance_graph.keys() {
-        if visited.get(node).is_none() {
-            let mut visited = visited.clone();
-            visited.insert(node.clone());
-
-            let distance_to = match current_location {
-                Some(cl) => *distance_graph.get(cl).unwrap().get(node).unwrap(),
-                None => 0,
-            };
-
-            let new_distance = current_distance + distance_to;
-            if new_distance < longest_distance {
-                return new_distance;
-            }
-
-            let distance_from_node = maximize_happiness_rec(
-                distance_graph,
-                &visited,
-                Some(&node),
-                new_distance,
-                longest_distance,
-            );
-
-            if distance_from_node > distance {
-                distance = distance_from_node;
-            }
+    let happiness = |a: usize, b: usize| {
+        happiness_graph.edge(a, b).unwrap_or(&0) + happiness_graph.edge(b, a).unwrap_or(&0)
+    };
+
+    let rest = (1..happiness_graph.len()).collect::<Vec<_>>();
+    let mut seatings = permutations(&rest);
+    let mut best = i32::MIN;
+
+    while let Some(seating) = seatings.next_slice() {
+        let mut total = happiness(0, seating[0]) + happiness(seating[seating.len() - 1], 0);
+        for pair in seating.windows(2) {
+            total += happiness(pair[0], pair[1]);
         }
+
+        best = best.max(total);
     }
 
-    distance
+    best
 }
 
 #[cfg(test)]
@@ -105,48 +79,29 @@ mod tests {
             "David would gain 41 happiness units by sitting next to Carol.",
         ];
 
-        let expected = HashMap::from([
-            (
-                "Alice".to_string(),
-                HashMap::from([
-                    ("Bob".to_string(), 54),
-                    ("Carol".to_string(), -79),
-                    ("David".to_string(), -2),
-                ]),
-            ),
-            (
-                "Bob".to_string(),
-                HashMap::from([
-                    ("Alice".to_string(), 83),
-                    ("Carol".to_string(), -7),
-                    ("David".to_string(), -63),
-                ]),
-            ),
-            (
-                "Carol".to_string(),
-                HashMap::from([
-                    ("Alice".to_string(), -62),
-                    ("Bob".to_string(), 60),
-                    ("David".to_string(), 55),
-                ]),
-            ),
-            (
-                "David".to_string(),
-                HashMap::from([
-                    ("Alice".to_string(), 46),
-                    ("Bob".to_string(), -7),
-                    ("Carol".to_string(), 41),
-                ]),
-            ),
-        ]);
-
         let actual = create_happiness_graph(&input);
-
-        assert_eq!(actual, expected);
+        let id = |name| actual.id(name).unwrap();
+
+        assert_eq!(actual.names(), ["Alice", "Bob", "Carol", "David"]);
+        for (a, b, expected) in [
+            ("Alice", "Bob", 54),
+            ("Alice", "Carol", -79),
+            ("Alice", "David", -2),
+            ("Bob", "Alice", 83),
+            ("Bob", "Carol", -7),
+            ("Bob", "David", -63),
+            ("Carol", "Alice", -62),
+            ("Carol", "Bob", 60),
+            ("Carol", "David", 55),
+            ("David", "Alice", 46),
+            ("David", "Bob", -7),
+            ("David", "Carol", 41),
+        ] {
+            assert_eq!(actual.edge(id(a), id(b)), Some(&expected), "{a} -> {b}");
+        }
     }
 
     #[test]
-    #[ignore = "TODO"]
     fn test_maximize_happiness() {
         #[rustfmt::skip]
         let input = vec![
@@ -1,15 +1,231 @@
-pub fn solve(_input: &[&str]) -> String {
-    let part1 = "";
-    let part2 = "";
+use anyhow::{Result, anyhow};
+use std::str::FromStr;
+
+use crate::util::vm::disassemble::Disassemble;
+use crate::util::vm::{Flow, InstructionSet, Machine};
+
+pub fn solve(input: &[&str]) -> String {
+    let part1 = run_program(input, 0)[1];
+    let part2 = run_program(input, 1)[1];
 
     format!(" Part1: {} \n Part2: {}", part1, part2)
 }
 
+#[derive(Debug, PartialEq, Eq, Clone)]
+enum Instruction {
+    Half(usize),
+    Triple(usize),
+    Increment(usize),
+    Jump(isize),
+    JumpIfEven(usize, isize),
+    JumpIfOne(usize, isize),
+}
+
+impl InstructionSet for Instruction {
+    type Word = u64;
+    const REGISTERS: usize = 2;
+
+    fn execute(&self, registers: &mut [u64], _output: &mut Vec<u64>) -> Result<Flow> {
+        match self {
+            Self::Half(r) => registers[*r] /= 2,
+            Self::Triple(r) => registers[*r] *= 3,
+            Self::Increment(r) => registers[*r] += 1,
+            Self::Jump(offset) => return Ok(Flow::Jump(*offset)),
+            Self::JumpIfEven(r, offset) if registers[*r].is_multiple_of(2) => {
+                return Ok(Flow::Jump(*offset));
+            }
+            Self::JumpIfOne(r, offset) if registers[*r] == 1 => return Ok(Flow::Jump(*offset)),
+            Self::JumpIfEven(..) | Self::JumpIfOne(..) => {}
+        }
+
+        Ok(Flow::Next)
+    }
+}
+
+fn register_name(register: usize) -> char {
+    (b'a' + register as u8) as char
+}
+
+impl Disassemble for Instruction {
+    fn jumps(&self) -> Vec<Flow> {
+        match self {
+            Self::Jump(offset) | Self::JumpIfEven(_, offset) | Self::JumpIfOne(_, offset) => {
+                vec![Flow::Jump(*offset)]
+            }
+            _ => vec![],
+        }
+    }
+
+    fn falls_through(&self) -> bool {
+        !matches!(self, Self::Jump(_))
+    }
+
+    fn describe(&self) -> String {
+        match self {
+            Self::Half(r) => format!("{0} = {0} / 2", register_name(*r)),
+            Self::Triple(r) => format!("{0} = {0} * 3", register_name(*r)),
+            Self::Increment(r) => format!("{0} = {0} + 1", register_name(*r)),
+            Self::Jump(offset) => format!("jump {offset:+}"),
+            Self::JumpIfEven(r, offset) => {
+                format!("if {} is even jump {offset:+}", register_name(*r))
+            }
+            Self::JumpIfOne(r, offset) => format!("if {} == 1 jump {offset:+}", register_name(*r)),
+        }
+    }
+
+    // The instruction as it is written in the input.
+    fn mnemonic(&self) -> String {
+        match self {
+            Self::Half(r) => format!("hlf {}", register_name(*r)),
+            Self::Triple(r) => format!("tpl {}", register_name(*r)),
+            Self::Increment(r) => format!("inc {}", register_name(*r)),
+            Self::Jump(offset) => format!("jmp {offset:+}"),
+            Self::JumpIfEven(r, offset) => format!("jie {}, {offset:+}", register_name(*r)),
+            Self::JumpIfOne(r, offset) => format!("jio {}, {offset:+}", register_name(*r)),
+        }
+    }
+}
+
+fn parse_register(register: &str) -> Result<usize> {
+    match register.trim() {
+        "a" => Ok(0),
+        "b" => Ok(1),
+        _ => Err(anyhow!("Unknown register `{}`", register)),
+    }
+}
+
+fn parse_offset(offset: &str) -> Result<isize> {
+    offset
+        .trim()
+        .parse::<isize>()
+        .map_err(|e| anyhow!("Could not parse `{}` as an offset: {}", offset, e))
+}
+
+impl FromStr for Instruction {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (opcode, arguments) = s
+            .split_once(' ')
+            .ok_or_else(|| anyhow!("Could not find the arguments in `{}`", s))?;
+
+        match opcode {
+            "hlf" => Ok(Self::Half(parse_register(arguments)?)),
+            "tpl" => Ok(Self::Triple(parse_register(arguments)?)),
+            "inc" => Ok(Self::Increment(parse_register(arguments)?)),
+            "jmp" => Ok(Self::Jump(parse_offset(arguments)?)),
+            "jie" | "jio" => {
+                let (register, offset) = arguments
+                    .split_once(',')
+                    .ok_or_else(|| anyhow!("Could not find the offset in `{}`", s))?;
+                let (register, offset) = (parse_register(register)?, parse_offset(offset)?);
+
+                Ok(match opcode {
+                    "jie" => Self::JumpIfEven(register, offset),
+                    _ => Self::JumpIfOne(register, offset),
+                })
+            }
+            _ => Err(anyhow!("Unknown instruction `{}`", s)),
+        }
+    }
+}
+
+// The registers a and b once the program finishes, starting from a = `a` and b = 0.
+fn run_program(input: &[&str], a: u64) -> [u64; 2] {
+    let mut lock = Machine::<Instruction>::parse(input)
+        .unwrap_or_else(|e| panic!("{e}"))
+        .with_registers(&[a]);
+
+    lock.run().unwrap_or_else(|e| panic!("{e}"));
+
+    [lock.registers[0], lock.registers[1]]
+}
+
 #[cfg(test)]
 mod tests {
 
-    // use super::*;
+    use super::*;
+    use crate::util::vm::disassemble::{Listing, Loop};
+    use pretty_assertions::assert_eq;
 
     #[test]
-    fn test_solve() {}
+    fn test_run_program() {
+        #[rustfmt::skip]
+        let input = [
+            "inc a",
+            "jio a, +2",
+            "tpl a",
+            "inc a",
+        ];
+
+        assert_eq!(run_program(&input, 0), [2, 0]);
+        assert_eq!(run_program(&input, 1), [7, 0]);
+    }
+
+    #[test]
+    fn test_run_program_collatz() {
+        // counts the steps for a to reach 1 in b
+        #[rustfmt::skip]
+        let input = [
+            "jio a, +8",
+            "inc b",
+            "jie a, +4",
+            "tpl a",
+            "inc a",
+            "jmp -5",
+            "hlf a",
+            "jmp -7",
+        ];
+
+        assert_eq!(run_program(&input, 6), [1, 8]);
+        assert_eq!(run_program(&input, 27), [1, 111]);
+        assert!(Instruction::from_str("jie c, +2").is_err());
+    }
+
+    #[test]
+    fn test_disassemble() {
+        #[rustfmt::skip]
+        let input = [
+            "jio a, +8",
+            "inc b",
+            "jie a, +4",
+            "tpl a",
+            "inc a",
+            "jmp -5",
+            "hlf a",
+            "jmp -7",
+            "jmp +2",
+            "inc a",
+            "inc b",
+        ];
+
+        let lock = Machine::<Instruction>::parse(&input).unwrap();
+        let listing = Listing::new(lock.program());
+
+        assert_eq!(
+            listing.loops,
+            [Loop { start: 0, end: 5 }, Loop { start: 0, end: 7 }]
+        );
+        assert_eq!(listing.unreachable, [9]);
+
+        #[rustfmt::skip]
+        let expected = [
+            "L0:",
+            "   0  jio a, +8  if a == 1 jump +8               ; -> L2",
+            "   1  inc b      b = b + 1",
+            "   2  jie a, +4  if a is even jump +4            ; -> L1",
+            "   3  tpl a      a = a * 3",
+            "   4  inc a      a = a + 1",
+            "   5  jmp -5     jump -5                         ; -> L0, loop",
+            "L1:",
+            "   6  hlf a      a = a / 2",
+            "   7  jmp -7     jump -7                         ; -> L0, loop",
+            "L2:",
+            "   8  jmp +2     jump +2                         ; -> L3",
+            "   9  inc a      a = a + 1                       ; unreachable",
+            "L3:",
+            "  10  inc b      b = b + 1",
+        ];
+        assert_eq!(listing.to_string().lines().collect::<Vec<_>>(), expected);
+    }
 }
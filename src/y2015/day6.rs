@@ -1,3 +1,5 @@
+use crate::util::bit_grid::BitGrid;
+use crate::util::grid::Point;
 use crate::util::grid_v1::Grid;
 
 pub fn solve(input: &[&str]) -> String {
@@ -25,7 +27,7 @@ enum ActionType {
 }
 
 fn process_lights(instructions: &[&str]) -> usize {
-    let mut light_grid = Grid::new(vec![false; 1000000], 1000, 1000);
+    let mut light_grid = BitGrid::new(1000, 1000);
 
     for instruction in instructions {
         let mut state = TokenType::Action;
@@ -86,20 +88,18 @@ fn process_lights(instructions: &[&str]) -> usize {
         //dbg!(end_y);
         //dbg!(action);
 
-        for x in start_x..=end_x {
-            for y in start_y..=end_y {
-                let idx = light_grid.xy_idx(x as i32, y as i32);
-                light_grid.spaces[idx] = match action {
-                    ActionType::Toggle => !light_grid.spaces[idx],
-                    ActionType::TurnOn => true,
-                    ActionType::TurnOff => false,
-                    _ => light_grid.spaces[idx],
-                }
-            }
+        let start = Point::new(start_x, start_y);
+        let end = Point::new(end_x, end_y);
+
+        match action {
+            ActionType::Toggle => light_grid.toggle_rect(&start, &end),
+            ActionType::TurnOn => light_grid.set_rect(&start, &end, true),
+            ActionType::TurnOff => light_grid.clear_rect(&start, &end),
+            _ => {}
         }
     }
 
-    light_grid.spaces.iter().filter(|state| **state).count()
+    light_grid.count_ones()
 }
 
 fn process_lights_v2(instructions: &[&str]) -> u32 {
@@ -1,5 +1,7 @@
 use std::collections::HashMap;
 
+use crate::util::memo::Memo;
+
 pub fn solve(input: &[&str]) -> String {
     let (part1, part2) = run_circuit(input);
 
@@ -99,66 +101,36 @@ fn parse_instructions<'a>(instructions: &[&'a str]) -> Result<HashMap<&'a str, O
 }
 
 fn evaluate(circuit: &HashMap<&str, Op>, wire: &str) -> u16 {
-    evaluate_rec(circuit, &mut HashMap::new(), wire)
+    evaluate_rec(circuit, &mut Memo::new(), wire)
 }
 
-fn evaluate_rec(circuit: &HashMap<&str, Op>, values: &mut HashMap<String, u16>, wire: &str) -> u16 {
-    if let Some(value) = values.get(wire) {
-        return *value;
-    }
-
+fn evaluate_rec(circuit: &HashMap<&str, Op>, values: &mut Memo<String, u16>, wire: &str) -> u16 {
     //dbg!(&wire);
     if let Ok(value) = wire.parse::<u16>() {
         //dbg!(&value);
         return value;
     }
 
-    let wire_value = circuit.get(wire).unwrap();
-    //dbg!(&wire_value);
-    match wire_value {
-        Op::Assignment(operand) => {
-            let value = evaluate_rec(circuit, values, operand);
-            values.insert(operand.to_string(), value);
-
-            value
+    values.get_or_insert_with(wire, |values| {
+        let wire_value = circuit.get(wire).unwrap();
+        //dbg!(&wire_value);
+        match wire_value {
+            Op::Assignment(operand) => evaluate_rec(circuit, values, operand),
+            Op::AND(lhs, rhs) => {
+                evaluate_rec(circuit, values, lhs) & evaluate_rec(circuit, values, rhs)
+            }
+            Op::OR(lhs, rhs) => {
+                evaluate_rec(circuit, values, lhs) | evaluate_rec(circuit, values, rhs)
+            }
+            Op::NOT(operand) => !evaluate_rec(circuit, values, operand),
+            Op::LSHIFT(operand, shift_ammount) => {
+                evaluate_rec(circuit, values, operand) << shift_ammount
+            }
+            Op::RSHIFT(operand, shift_ammount) => {
+                evaluate_rec(circuit, values, operand) >> shift_ammount
+            }
         }
-        Op::AND(lhs, rhs) => {
-            let lhv = evaluate_rec(circuit, values, lhs);
-            values.insert(lhs.to_string(), lhv);
-
-            let rhv = evaluate_rec(circuit, values, rhs);
-            values.insert(rhs.to_string(), rhv);
-
-            lhv & rhv
-        }
-        Op::OR(lhs, rhs) => {
-            let lhv = evaluate_rec(circuit, values, lhs);
-            values.insert(lhs.to_string(), lhv);
-
-            let rhv = evaluate_rec(circuit, values, rhs);
-            values.insert(rhs.to_string(), rhv);
-
-            lhv | rhv
-        }
-        Op::NOT(operand) => {
-            let value = evaluate_rec(circuit, values, operand);
-            values.insert(operand.to_string(), value);
-
-            !value
-        }
-        Op::LSHIFT(operand, shift_ammount) => {
-            let value = evaluate_rec(circuit, values, operand);
-            values.insert(operand.to_string(), value);
-
-            value << shift_ammount
-        }
-        Op::RSHIFT(operand, shift_ammount) => {
-            let value = evaluate_rec(circuit, values, operand);
-            values.insert(operand.to_string(), value);
-
-            value >> shift_ammount
-        }
-    }
+    })
 }
 
 #[cfg(test)]
@@ -1,4 +1,5 @@
-use std::collections::{HashMap, HashSet};
+use crate::util::combinatorics::{Route, held_karp};
+use crate::util::graph::Graph;
 
 pub fn solve(input: &[&str]) -> String {
     let distance_graph = parse_distances(input);
@@ -8,118 +9,47 @@ pub fn solve(input: &[&str]) -> String {
     format!(" Part1: {} \n Part2: {}", part1, part2)
 }
 
-fn parse_distances(distances: &[&str]) -> HashMap<String, HashMap<String, u32>> {
-    let mut distance_graph = HashMap::new();
-
-    for distance in distances {
-        let source = distance.split(' ').nth(0).unwrap().to_string();
-        let destination = distance.split(' ').nth(2).unwrap().to_string();
-        let magnitude = distance.split(' ').nth(4).unwrap().parse::<u32>().unwrap();
-
-        (*distance_graph
-            .entry(source.clone())
-            .or_insert(HashMap::new()))
-        .insert(destination.clone(), magnitude);
-        (*distance_graph.entry(destination).or_insert(HashMap::new())).insert(source, magnitude);
-    }
-
-    distance_graph
+fn parse_distances(distances: &[&str]) -> Graph<(), u32> {
+    Graph::parse_weighted_edges(distances).unwrap_or_else(|e| panic!("{}", e))
 }
 
-fn traveling_santaman(distance_graph: &HashMap<String, HashMap<String, u32>>) -> u32 {
-    traveling_santaman_rec(distance_graph, &HashSet::new(), None, 0, u32::MAX)
+// The direct distance between every pair of locations. Santa has to visit each location
+// exactly once, so a shorter way round through another location doesn't count.
+fn distance_matrix(distance_graph: &Graph<(), u32>) -> Vec<Vec<i64>> {
+    distance_graph
+        .adjacency_matrix()
+        .into_iter()
+        .enumerate()
+        .map(|(from, row)| {
+            row.into_iter()
+                .enumerate()
+                .map(|(to, d)| match d {
+                    Some(d) => d as i64,
+                    None if from == to => 0,
+                    None => panic!(
+                        "No distance between {} and {}",
+                        distance_graph.name(from),
+                        distance_graph.name(to)
+                    ),
+                })
+                .collect()
+        })
+        .collect()
 }
 
-fn traveling_santaman_rec(
-    distance_graph: &HashMap<String, HashMap<String, u32>>,
-    visited: &HashSet<String>,
-    current_location: Option<&str>,
-    current_distance: u32,
-    shortest_distance: u32,
-) -> u32 {
-    if visited.len() == distance_graph.len() {
-        return current_distance;
-    }
-
-    let mut distance = u32::MAX;
-    for node in distance_graph.keys() {
-        if visited.get(node).is_none() {
-            let mut visited = visited.clone();
-            visited.insert(node.clone());
-
-            let distance_to = match current_location {
-                Some(cl) => *distance_graph.get(cl).unwrap().get(node).unwrap(),
-                None => 0,
-            };
-
-            let new_distance = current_distance + distance_to;
-            if new_distance > shortest_distance {
-                return new_distance;
-            }
-
-            let distance_from_node = traveling_santaman_rec(
-                distance_graph,
-                &visited,
-                Some(&node),
-                new_distance,
-                shortest_distance,
-            );
-
-            if distance_from_node < distance {
-                distance = distance_from_node;
-            }
-        }
-    }
+fn traveling_santaman(distance_graph: &Graph<(), u32>) -> u32 {
+    let distances = distance_matrix(distance_graph);
 
-    distance
+    held_karp(&distances, Route::Path).unwrap_or(0) as u32
 }
 
-fn show_off_santaman(distance_graph: &HashMap<String, HashMap<String, u32>>) -> u32 {
-    show_off_santaman_rec(distance_graph, &HashSet::new(), None, 0, 0)
-}
-
-fn show_off_santaman_rec(
-    distance_graph: &HashMap<String, HashMap<String, u32>>,
-    visited: &HashSet<String>,
-    current_location: Option<&str>,
-    current_distance: u32,
-    longest_distance: u32,
-) -> u32 {
-    if visited.len() == distance_graph.len() {
-        return current_distance;
-    }
-
-    let mut distance = 0;
-    for node in distance_graph.keys() {
-        if visited.get(node).is_none() {
-            let mut visited = visited.clone();
-            visited.insert(node.clone());
-
-            let distance_to = match current_location {
-                Some(cl) => *distance_graph.get(cl).unwrap().get(node).unwrap(),
-                None => 0,
-            };
-
-            let new_distance = current_distance + distance_to;
-            if new_distance < longest_distance {
-                return new_distance;
-            }
-
-            let distance_from_node = show_off_santaman_rec(
-                distance_graph,
-                &visited,
-                Some(&node),
-                new_distance,
-                longest_distance,
-            );
-
-            if distance_from_node > distance {
-                distance = distance_from_node;
-            }
-        }
-    }
+fn show_off_santaman(distance_graph: &Graph<(), u32>) -> u32 {
+    let negated = distance_matrix(distance_graph)
+        .into_iter()
+        .map(|row| row.into_iter().map(|d| -d).collect())
+        .collect::<Vec<_>>();
 
-    distance
+    (-held_karp(&negated, Route::Path).unwrap_or(0)) as u32
 }
 
 #[cfg(test)]
@@ -136,23 +66,18 @@ mod tests {
             "Dublin to Belfast = 141",
         ];
 
-        let expected = HashMap::from([
-            (
-                "London".to_string(),
-                HashMap::from([("Belfast".to_string(), 518), ("Dublin".to_string(), 464)]),
-            ),
-            (
-                "Dublin".to_string(),
-                HashMap::from([("Belfast".to_string(), 141), ("London".to_string(), 464)]),
-            ),
-            (
-                "Belfast".to_string(),
-                HashMap::from([("Dublin".to_string(), 141), ("London".to_string(), 518)]),
-            ),
-        ]);
-
         let actual = parse_distances(&distances);
-        assert_eq!(expected, actual);
+        let id = |name| actual.id(name).unwrap();
+
+        assert_eq!(actual.len(), 3);
+        for (a, b, expected) in [
+            ("London", "Dublin", 464),
+            ("London", "Belfast", 518),
+            ("Dublin", "Belfast", 141),
+        ] {
+            assert_eq!(actual.edge(id(a), id(b)), Some(&expected));
+            assert_eq!(actual.edge(id(b), id(a)), Some(&expected));
+        }
     }
 
     #[test]
@@ -188,4 +113,20 @@ mod tests {
 
         assert_eq!(expected, actual);
     }
+
+    #[test]
+    fn test_no_shortcuts() {
+        // going from A to B through C is much shorter, but would visit C twice
+        #[rustfmt::skip]
+        let input = vec![
+            "A to B = 100",
+            "A to C = 1",
+            "B to C = 1",
+        ];
+
+        let distances = parse_distances(&input);
+
+        assert_eq!(traveling_santaman(&distances), 2);
+        assert_eq!(show_off_santaman(&distances), 101);
+    }
 }
@@ -17,6 +17,7 @@ mod day23;
 mod day24;
 mod day25;
 mod day3;
+#[cfg(feature = "md5")]
 mod day4;
 mod day5;
 mod day6;
@@ -29,7 +30,10 @@ pub fn run_day(day: &str, input: &[&str]) -> String {
         "1" => day1::solve(&input),
         "2" => day2::solve(&input),
         "3" => day3::solve(&input),
+        #[cfg(feature = "md5")]
         "4" => day4::solve(&input),
+        #[cfg(not(feature = "md5"))]
+        "4" => format!("Day {} requires feature md5", day),
         "5" => day5::solve(&input),
         "6" => day6::solve(&input),
         "7" => day7::solve(&input),
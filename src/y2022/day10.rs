@@ -1,4 +1,8 @@
+use anyhow::{Result, anyhow};
+use std::str::FromStr;
+
 use crate::util::grid_v1::Grid;
+use crate::util::vm::{Flow, InstructionSet, Machine};
 
 pub fn solve(input: &[&str]) -> String {
     let part1 = sum_signal_strengths(input);
@@ -7,29 +11,57 @@ pub fn solve(input: &[&str]) -> String {
     format!(" Part1: {} \n Part2: {:#?}", part1, part2)
 }
 
-fn run_instructions(instructions: &[&str]) -> Vec<i32> {
-    let mut x = 1;
-    let mut cycles = vec![x];
-
-    for instruction in instructions {
-        if instruction.starts_with("noop") {
-            cycles.push(x);
-        } else if instruction.starts_with("addx") {
-            let (_, value) = instruction.split_once(' ').unwrap();
-            let value = value.parse::<i32>().unwrap_or_else(|e| {
-                panic!(
-                    "Could not parse `{}` from `{}` as i32: {}",
-                    value, instruction, e
-                )
-            });
-
-            cycles.push(x);
-            cycles.push(x);
-            x += value;
+#[derive(Debug, PartialEq, Eq, Clone)]
+enum Instruction {
+    Noop,
+    Addx(i32),
+}
+
+impl InstructionSet for Instruction {
+    type Word = i32;
+    const REGISTERS: usize = 1;
+
+    fn cycles(&self) -> usize {
+        match self {
+            Self::Noop => 1,
+            Self::Addx(_) => 2,
         }
     }
 
-    cycles.push(x);
+    fn execute(&self, registers: &mut [i32], _output: &mut Vec<i32>) -> Result<Flow> {
+        if let Self::Addx(value) = self {
+            registers[0] += value;
+        }
+
+        Ok(Flow::Next)
+    }
+}
+
+impl FromStr for Instruction {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.split_once(' ') {
+            None if s == "noop" => Ok(Self::Noop),
+            Some(("addx", value)) => value
+                .parse::<i32>()
+                .map(Self::Addx)
+                .map_err(|e| anyhow!("Could not parse `{}` from `{}` as i32: {}", value, s, e)),
+            _ => Err(anyhow!("Unknown instruction `{}`", s)),
+        }
+    }
+}
+
+// The value of X before the first cycle, during each cycle, then once the program is done.
+fn run_instructions(instructions: &[&str]) -> Vec<i32> {
+    let mut cpu = Machine::<Instruction>::parse(instructions)
+        .unwrap_or_else(|e| panic!("{e}"))
+        .with_registers(&[1]);
+    let mut cycles = vec![1];
+
+    cpu.run_observing(|_, registers| cycles.push(registers[0]))
+        .unwrap_or_else(|e| panic!("{e}"));
+    cycles.push(cpu.registers[0]);
 
     cycles
 }
@@ -1,4 +1,4 @@
-use crate::algorithms::least_common_multiple;
+use crate::util::number_theory::least_common_multiple;
 use std::fmt;
 
 pub fn solve(input: &[&str]) -> String {
@@ -1,4 +1,7 @@
+use std::ops::Range;
+
 use crate::util::grid_v1::Point;
+use crate::util::intervals::RangeSet;
 
 pub fn solve(input: &[&str]) -> String {
     let part1 = count_exclusions(input, 2_000_000);
@@ -80,6 +83,13 @@ impl Sensor {
 
         Some(self.position.x + self.radius - (self.position.y - y).abs())
     }
+
+    fn get_x_span(&self, y: i32) -> Option<Range<i32>> {
+        let end = self.get_x_endpoint(y)?;
+        let start = 2 * self.position.x - end;
+
+        Some(start..end + 1)
+    }
 }
 
 fn count_exclusions(input: &[&str], y: i32) -> i32 {
@@ -88,49 +98,18 @@ fn count_exclusions(input: &[&str], y: i32) -> i32 {
         .map(|l| Sensor::parse_sensor(l))
         .collect::<Vec<Sensor>>();
 
-    let mut min_x = i32::MAX;
-    let mut max_x = 0;
-    for sensor in sensors.iter() {
-        let sensor_min_x = sensor.position.x - sensor.radius;
-        if sensor_min_x < min_x {
-            min_x = sensor_min_x;
-        }
-
-        let sensor_max_x = sensor.position.x + sensor.radius;
-        if sensor_max_x > max_x {
-            max_x = sensor_max_x;
-        }
-    }
-
-    let mut exclusion_count = 0;
-    dbg!(max_x);
-    // let mut exclusions = vec![];
-
-    for x in min_x..=max_x {
-        let position = Point::new(x, y);
-
-        // let mut excluded = false;
+    let mut exclusions = sensors
+        .iter()
+        .filter_map(|sensor| sensor.get_x_span(y))
+        .collect::<RangeSet<i32>>();
 
-        for sensor in sensors.iter() {
-            if sensor.position.manhattan_distance(position) <= sensor.radius
-                && position != sensor.beacon
-            {
-                exclusion_count += 1;
-                // excluded = true;
-                break;
-            }
+    for sensor in sensors.iter() {
+        if sensor.beacon.y == y {
+            exclusions.remove(sensor.beacon.x..sensor.beacon.x + 1);
         }
-
-        // if excluded {
-        // exclusions.push('#');
-        // } else {
-        // exclusions.push('.');
-        // }
     }
 
-    // dbg!(exclusions);
-
-    exclusion_count
+    exclusions.total_len()
 }
 
 fn tuning_frequency(input: &[&str], search_max: i32) -> u64 {
@@ -1,20 +1,15 @@
-use std::collections::{HashMap, HashSet};
+use crate::util::graph::Graph;
 
-pub fn solve(_input: &[&str]) -> String {
-    let part1 = "";
+pub fn solve(input: &[&str]) -> String {
+    let part1 = find_max_flow_rate(input);
     let part2 = "";
 
     format!(" Part1: {} \n Part2: {}", part1, part2)
 }
 
-#[derive(Debug, PartialEq, Eq)]
-struct Node {
-    value: usize,
-    adjacency: Vec<String>,
-}
-
-fn parse_input(input: &[&str]) -> HashMap<String, Node> {
-    let mut graph = HashMap::new();
+// Valves are nodes holding their flow rate, tunnels are edges.
+fn parse_input(input: &[&str]) -> Graph<usize, ()> {
+    let mut graph = Graph::new();
 
     for line in input {
         let mut tokens = line.split_whitespace();
@@ -34,68 +29,64 @@ fn parse_input(input: &[&str]) -> HashMap<String, Node> {
         tokens.next();
         tokens.next();
         tokens.next();
-        let mut adjacency = Vec::new();
+
+        let from = graph.add_node(label, value);
         for tunnel in tokens {
-            adjacency.push(tunnel.replace(',', ""));
+            let to = graph.node_id(&tunnel.replace(',', ""));
+            graph.add_edge(from, to, ());
         }
-
-        graph.insert(label.to_string(), Node { value, adjacency });
     }
 
     graph
 }
 
-#[allow(dead_code)]
 fn find_max_flow_rate(input: &[&str]) -> usize {
     let graph = parse_input(input);
+    let distances = graph.floyd_warshall(|_| 1);
 
-    max_flow_rate("AA", &graph, 30, &HashSet::new())
+    // only valves that release something are worth walking to
+    let valves = (0..graph.len())
+        .filter(|valve| *graph.node(*valve) > 0)
+        .collect::<Vec<_>>();
+
+    let start = graph.id("AA").unwrap();
+
+    max_flow_rate(start, &graph, &distances, &valves, 30, 0)
 }
 
+// The most pressure that can be released by walking straight from one closed valve to the
+// next and opening it, `opened` has a bit set for each valve already open.
 fn max_flow_rate(
-    from: &str,
-    graph: &HashMap<String, Node>,
+    from: usize,
+    graph: &Graph<usize, ()>,
+    distances: &[Vec<Option<usize>>],
+    valves: &[usize],
     time_remaining: usize,
-    visited: &HashSet<String>,
+    opened: u64,
 ) -> usize {
-    let mut visited = visited.clone();
-
-    let current_node = graph.get(from).unwrap();
-
-    let mut results = Vec::new();
-
-    let flow_rate = current_node.value;
-
-    let total_flow = flow_rate * (time_remaining.saturating_sub(1));
-
-    for label in current_node.adjacency.iter() {
-        if !visited.contains(label) {
-            if time_remaining >= 1 {
-                results.push((
-                    label,
-                    max_flow_rate(label, graph, time_remaining - 1, &visited),
-                ));
-            }
-            if flow_rate > 0 && time_remaining >= 2 {
-                visited.insert(from.to_string());
-                results.push((
-                    label,
-                    total_flow + max_flow_rate(label, graph, time_remaining - 2, &visited),
-                ));
-            }
-        }
-    }
-
-    let l = "stay".to_string();
-    results.push((&l, total_flow));
-
-    results.sort_by_key(|e| e.1);
-
-    dbg!(from);
-    dbg!(&current_node);
-    dbg!(&results);
-
-    results.pop().unwrap().1
+    valves
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| opened & 1 << i == 0)
+        .filter_map(|(i, valve)| {
+            // walk there and spend a minute opening it
+            let time_taken = distances[from][*valve]? + 1;
+            let time_remaining = time_remaining.checked_sub(time_taken)?;
+
+            Some(
+                graph.node(*valve) * time_remaining
+                    + max_flow_rate(
+                        *valve,
+                        graph,
+                        distances,
+                        valves,
+                        time_remaining,
+                        opened | 1 << i,
+                    ),
+            )
+        })
+        .max()
+        .unwrap_or(0)
 }
 
 #[cfg(test)]
@@ -119,31 +110,41 @@ mod tests {
             "Valve JJ has flow rate=21; tunnel leads to valve II",
         ];
 
-        #[rustfmt::skip]
-        let expected = HashMap::from([
-            ("AA".to_string(), Node { value: 0,  adjacency: vec!["DD".to_string(), "II".to_string(), "BB".to_string()]}),
-            ("BB".to_string(), Node { value: 13, adjacency: vec!["CC".to_string(), "AA".to_string()]}),
-            ("CC".to_string(), Node { value: 2,  adjacency: vec!["DD".to_string(), "BB".to_string()]}),
-            ("DD".to_string(), Node { value: 20, adjacency: vec!["CC".to_string(), "AA".to_string(), "EE".to_string()]}),
-            ("EE".to_string(), Node { value: 3,  adjacency: vec!["FF".to_string(), "DD".to_string()]}),
-            ("FF".to_string(), Node { value: 0,  adjacency: vec!["EE".to_string(), "GG".to_string()]}),
-            ("GG".to_string(), Node { value: 0,  adjacency: vec!["FF".to_string(), "HH".to_string()]}),
-            ("HH".to_string(), Node { value: 22, adjacency: vec!["GG".to_string()]}),
-            ("II".to_string(), Node { value: 0,  adjacency: vec!["AA".to_string(), "JJ".to_string()]}),
-            ("JJ".to_string(), Node { value: 21, adjacency: vec!["II".to_string()]}),
-        ]);
-
         let actual = parse_input(&input);
 
-        assert_eq!(
-            actual, expected,
-            "\n Got `{:#?}` when expecting `{:#?}` from calling parse_input on {:#?}",
-            actual, expected, input
-        );
+        #[rustfmt::skip]
+        let expected = [
+            ("AA", 0,  vec!["DD", "II", "BB"]),
+            ("BB", 13, vec!["CC", "AA"]),
+            ("CC", 2,  vec!["DD", "BB"]),
+            ("DD", 20, vec!["CC", "AA", "EE"]),
+            ("EE", 3,  vec!["FF", "DD"]),
+            ("FF", 0,  vec!["EE", "GG"]),
+            ("GG", 0,  vec!["FF", "HH"]),
+            ("HH", 22, vec!["GG"]),
+            ("II", 0,  vec!["AA", "JJ"]),
+            ("JJ", 21, vec!["II"]),
+        ];
+
+        assert_eq!(actual.len(), expected.len());
+        for (label, value, adjacency) in expected {
+            let id = actual.id(label).unwrap();
+            let tunnels = actual
+                .neighbours(id)
+                .map(|(to, _)| actual.name(to))
+                .collect::<Vec<_>>();
+
+            assert_eq!(
+                (*actual.node(id), tunnels),
+                (value, adjacency),
+                "\n Unexpected valve `{}` from calling parse_input on {:#?}",
+                label,
+                input
+            );
+        }
     }
 
     #[test]
-    #[ignore = "Run Time"]
     fn test_find_max_flow_rate() {
         #[rustfmt::skip]
         let input = [
@@ -176,22 +177,6 @@ mod tests {
             actual, expected, input
         );
 
-        let graph = HashMap::from([(
-            "CC".to_string(),
-            Node {
-                value: 2,
-                adjacency: vec![],
-            },
-        )]);
-        let time_remaining = 5;
-        let actual = max_flow_rate("CC", &graph, time_remaining + 1, &HashSet::new());
-        let expected = graph.get("CC").unwrap().value * time_remaining;
-        assert_eq!(
-            actual, expected,
-            "\n Got `{:#?}` when expecting `{:#?}`",
-            actual, expected
-        );
-
         #[rustfmt::skip]
         let input = [
             "Valve AA has flow rate=0; tunnels lead to valves DD, II, BB",
@@ -1,16 +1,192 @@
-pub fn solve(_input: &[&str]) -> String {
-    let part1 = "";
-    let part2 = "";
+use crate::util::cube_net::CubeNet;
+use crate::util::point::{GridDirection, Point};
+
+pub fn solve(input: &[&str]) -> String {
+    let part1 = solve_part_1(input);
+    let part2 = solve_part_2(input);
 
     format!(" Part1: {} \n Part2: {}", part1, part2)
 }
 
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+enum Instruction {
+    Move(usize),
+    TurnLeft,
+    TurnRight,
+}
+
+// The map, a blank line, then the path like `10R5L5`.
+fn parse_notes<'a>(input: &[&'a str]) -> (Vec<&'a str>, Vec<Instruction>) {
+    let blank = input
+        .iter()
+        .position(|line| line.trim().is_empty())
+        .unwrap_or_else(|| panic!("Parse Error - Could not find the blank line after the map"));
+
+    let map = input[..blank].to_vec();
+    let path = input
+        .get(blank + 1)
+        .unwrap_or_else(|| panic!("Parse Error - Could not find the path after the map"))
+        .trim();
+
+    let mut instructions = vec![];
+    let mut steps = String::new();
+    for ch in path.chars().chain(std::iter::once(' ')) {
+        if ch.is_ascii_digit() {
+            steps.push(ch);
+            continue;
+        }
+
+        if !steps.is_empty() {
+            instructions.push(Instruction::Move(steps.parse().unwrap()));
+            steps.clear();
+        }
+
+        match ch {
+            'L' => instructions.push(Instruction::TurnLeft),
+            'R' => instructions.push(Instruction::TurnRight),
+            ' ' => {}
+            _ => panic!("Parse Error - Unexpected `{}` in the path", ch),
+        }
+    }
+
+    (map, instructions)
+}
+
+fn tile(map: &[&str], point: Point) -> Option<u8> {
+    map.get(point.y)
+        .and_then(|row| row.as_bytes().get(point.x))
+        .copied()
+        .filter(|tile| *tile != b' ')
+}
+
+// Walks the path from the leftmost open tile of the top row, `step` says where one move
+// forwards goes, and returns the final password.
+fn follow_path<F>(map: &[&str], instructions: &[Instruction], step: F) -> usize
+where
+    F: Fn(Point, GridDirection) -> (Point, GridDirection),
+{
+    let start = map[0]
+        .find('.')
+        .unwrap_or_else(|| panic!("Could not find an open tile on the top row"));
+    let mut position = Point::new(start, 0);
+    let mut facing = GridDirection::Right;
+
+    for instruction in instructions {
+        match instruction {
+            Instruction::TurnLeft => facing = facing.turn_counter_clockwise_90(),
+            Instruction::TurnRight => facing = facing.turn_clockwise_90(),
+            Instruction::Move(steps) => {
+                for _ in 0..*steps {
+                    let (next, turned) = step(position, facing);
+                    if tile(map, next) == Some(b'#') {
+                        break;
+                    }
+
+                    (position, facing) = (next, turned);
+                }
+            }
+        }
+    }
+
+    let facing = match facing {
+        GridDirection::Right => 0,
+        GridDirection::Down => 1,
+        GridDirection::Left => 2,
+        GridDirection::Up => 3,
+        _ => unreachable!(),
+    };
+
+    1000 * (position.y + 1) + 4 * (position.x + 1) + facing
+}
+
+// Falling off the map comes back in on the far side of the same row or column.
+fn solve_part_1(input: &[&str]) -> usize {
+    let (map, instructions) = parse_notes(input);
+
+    follow_path(&map, &instructions, |position, facing| {
+        if let Some(next) = position.get_adjacent(facing)
+            && tile(&map, next).is_some()
+        {
+            return (next, facing);
+        }
+
+        let mut wrapped = position;
+        while let Some(previous) = wrapped.get_adjacent(facing.opposite())
+            && tile(&map, previous).is_some()
+        {
+            wrapped = previous;
+        }
+
+        (wrapped, facing)
+    })
+}
+
+// The map is really the net of a cube, falling off an edge carries on around it.
+fn solve_part_2(input: &[&str]) -> usize {
+    let (map, instructions) = parse_notes(input);
+    let net = CubeNet::new(&map).unwrap_or_else(|e| panic!("{e}"));
+
+    follow_path(&map, &instructions, |position, facing| {
+        net.step(position, facing).unwrap()
+    })
+}
+
 #[cfg(test)]
 mod tests {
 
-    #![allow(unused_imports)]
     use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[rustfmt::skip]
+    const INPUT: [&str; 14] = [
+        "        ...#",
+        "        .#..",
+        "        #...",
+        "        ....",
+        "...#.......#",
+        "........#...",
+        "..#....#....",
+        "..........#.",
+        "        ...#....",
+        "        .....#..",
+        "        .#......",
+        "        ......#.",
+        "",
+        "10R5L5R10L4R5L5",
+    ];
 
     #[test]
-    fn test_solve() {}
+    fn test_parse_notes() {
+        let (map, instructions) = parse_notes(&INPUT);
+
+        assert_eq!(map.len(), 12);
+        assert_eq!(
+            instructions[..4],
+            [
+                Instruction::Move(10),
+                Instruction::TurnRight,
+                Instruction::Move(5),
+                Instruction::TurnLeft
+            ]
+        );
+        assert_eq!(instructions.len(), 13);
+    }
+
+    #[test]
+    fn test_solve_part_1() {
+        let expected = 6032;
+
+        let actual = solve_part_1(&INPUT);
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_solve_part_2() {
+        let expected = 5031;
+
+        let actual = solve_part_2(&INPUT);
+
+        assert_eq!(actual, expected);
+    }
 }
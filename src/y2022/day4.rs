@@ -1,3 +1,5 @@
+use crate::util::intervals::RangeSet;
+
 pub fn solve(input: &[&str]) -> String {
     let part1 = count_contained_intervals(input);
     let part2 = count_overlapping_intervals(input);
@@ -26,17 +28,18 @@ fn count_overlapping_intervals(input: &[&str]) -> u32 {
 }
 
 fn are_intervals_fully_contained(intervals: &str) -> Option<bool> {
-    let vals = intervals
-        .split(&['-', ','])
-        .map(|v| v.parse::<u32>().unwrap())
-        .collect::<Vec<_>>();
+    let (a, b) = parse_intervals(intervals);
 
-    let (a_start, a_end, b_start, b_end) = (vals[0], vals[1], vals[2], vals[3]);
-
-    Some((a_start <= b_start && a_end >= b_end) || (b_start <= a_start && b_end >= a_end))
+    Some(a.difference(&b).is_empty() || b.difference(&a).is_empty())
 }
 
 fn do_intervals_overlap(intervals: &str) -> Option<bool> {
+    let (a, b) = parse_intervals(intervals);
+
+    Some(!a.intersection(&b).is_empty())
+}
+
+fn parse_intervals(intervals: &str) -> (RangeSet<u32>, RangeSet<u32>) {
     let vals = intervals
         .split(&['-', ','])
         .map(|v| v.parse::<u32>().unwrap())
@@ -44,11 +47,9 @@ fn do_intervals_overlap(intervals: &str) -> Option<bool> {
 
     let (a_start, a_end, b_start, b_end) = (vals[0], vals[1], vals[2], vals[3]);
 
-    Some(
-        (a_start >= b_start && a_start <= b_end)
-            || (a_end >= b_start && a_end <= b_end)
-            || (b_start >= a_start && b_start <= a_end)
-            || (b_end >= a_start && b_end <= a_end),
+    (
+        RangeSet::from_range(a_start..a_end + 1),
+        RangeSet::from_range(b_start..b_end + 1),
     )
 }
 
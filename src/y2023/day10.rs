@@ -1,10 +1,6 @@
-use std::{
-    collections::{HashMap, HashSet},
-    fmt::Display,
-    iter::FromIterator,
-    thread,
-};
+use std::{collections::HashMap, fmt::Display, thread};
 
+use crate::util::geometry::interior_points;
 use crate::util::grid::{Grid, GridDirection, Point};
 
 pub fn solve(input: &[&str]) -> String {
@@ -22,60 +18,18 @@ fn furthest_point_steps(pipe_data: &[&str]) -> usize {
 }
 
 fn enclosed_tile_count(pipe_data: &[&str]) -> usize {
-    let mut pipe_grid = parse_pipe_grid(pipe_data);
-
-    let path = find_path(&pipe_grid);
-    // dbg!(&path);
-
-    let start_point = path.last().unwrap();
-    let start_node = pipe_grid.get_mut(&start_point).unwrap();
-    start_node.tile = {
-        let connections: HashSet<GridDirection> =
-            HashSet::from_iter(start_node.connections.clone());
-        if connections == HashSet::from_iter([GridDirection::Up, GridDirection::Down]) {
-            '|'
-        } else if connections == HashSet::from_iter([GridDirection::Left, GridDirection::Right]) {
-            '-'
-        } else if connections == HashSet::from_iter([GridDirection::Up, GridDirection::Left]) {
-            'L'
-        } else if connections == HashSet::from_iter([GridDirection::Up, GridDirection::Right]) {
-            'J'
-        } else if connections == HashSet::from_iter([GridDirection::Left, GridDirection::Down]) {
-            '7'
-        } else if connections == HashSet::from_iter([GridDirection::Right, GridDirection::Down]) {
-            'F'
-        } else {
-            unreachable!()
-        }
-    };
-
-    let path: HashSet<&Point> = HashSet::from_iter(path.iter());
-
-    let mut is_inside = false;
-    let mut inside_outside = vec![];
-    let verticals: HashSet<char> = HashSet::from_iter(['|', 'J', 'L']);
-
-    for (i, node) in pipe_grid.iter().enumerate() {
-        let point = pipe_grid.idx_point(i);
-        if path.contains(&point) {
-            if verticals.contains(&node.tile) {
-                is_inside = !is_inside;
-            }
-        } else {
-            inside_outside.push((point, is_inside));
-        }
-    }
+    let pipe_grid = parse_pipe_grid(pipe_data);
 
-    for (point, is_inside) in inside_outside {
-        pipe_grid.get_mut(&point).unwrap().tile = match is_inside {
-            true => 'I',
-            false => 'O',
-        };
-    }
+    // the path ends back on the start, which is already its first point
+    let mut path = find_path(&pipe_grid);
+    path.pop();
 
-    dbg!(&pipe_grid);
+    let vertices = path
+        .iter()
+        .map(|point| Point::new(point.x as i64, point.y as i64))
+        .collect::<Vec<_>>();
 
-    pipe_grid.iter().filter(|node| node.tile == 'I').count()
+    interior_points(&vertices) as usize
 }
 
 fn find_path(pipe_grid: &Grid<Node>) -> Vec<Point> {
@@ -1,78 +1,88 @@
-use std::{str::FromStr, usize};
+use crate::util::memo::{Memo, MemoStats};
 
 pub fn solve(input: &[&str]) -> String {
-    let part1 = total_combinations(&input);
-    let part2 = total_combinations_unfolded(&input);
+    let (part1, part1_stats) = total_combinations(input);
+    let (part2, part2_stats) = total_combinations_unfolded(input);
 
-    format!(" Part1: {} \n Part2: {}", part1, part2)
+    let mut output = format!(" Part1: {} \n Part2: {}", part1, part2);
+    if cfg!(feature = "memo-stats") {
+        output += &format!("\n Part1 memo: {part1_stats}\n Part2 memo: {part2_stats}");
+    }
+
+    output
 }
 
-fn total_combinations_unfolded(input: &[&str]) -> usize {
-    input
-        .iter()
-        .map(|line| {
-            let (conditions, counts) = line.split_once(' ').unwrap();
-            let mut unfolded = String::from_str(&conditions).unwrap();
-            unfolded.push('?');
-            unfolded = unfolded.repeat(5);
-            let _ = unfolded.pop();
-
-            let counts = counts
-                .split(',')
-                .map(|d| d.parse::<usize>().unwrap())
-                .collect::<Vec<_>>();
-
-            count_arrangements(&unfolded, &counts.repeat(5))
-        })
-        .sum()
+fn parse_record(line: &str) -> (&str, Vec<usize>) {
+    let (conditions, counts) = line.split_once(' ').unwrap();
+    let counts = counts
+        .split(',')
+        .map(|d| d.parse::<usize>().unwrap())
+        .collect::<Vec<_>>();
+
+    (conditions, counts)
 }
 
-fn total_combinations(input: &[&str]) -> usize {
-    input
-        .iter()
-        .map(|line| {
-            let (conditions, counts) = line.split_once(' ').unwrap();
-            let counts = counts
-                .split(',')
-                .map(|d| d.parse::<usize>().unwrap())
-                .collect::<Vec<_>>();
-            (conditions, counts)
-        })
-        .map(|(conditions, counts)| count_arrangements(conditions, &counts))
-        .sum()
+fn total_combinations_unfolded(input: &[&str]) -> (usize, MemoStats) {
+    sum_arrangements(input.iter().map(|line| {
+        let (conditions, counts) = parse_record(line);
+        ([conditions; 5].join("?"), counts.repeat(5))
+    }))
 }
 
-// copied from https://github.com/akaritakai/AdventOfCode2023/blob/128d0f06c2d3cb67bfad63a599aea92e4bbf7ccf/src/day12.rs#L54
-fn count_arrangements(line: &str, counts: &[usize]) -> usize {
-    let line = line.as_bytes();
-    let n = line.len();
-    let m = counts.len();
-    let mut dp = &mut vec![vec![0; n + 1]; m + 1];
-    let mut next_dp = &mut vec![vec![0; n + 1]; m + 1];
-
-    dp[m][0] = 1;
-    dp[m - 1][counts[m - 1]] = 1;
-
-    for pos in (0..n).rev() {
-        for group in 0..=m {
-            let max_count = if group == m { 0 } else { counts[group] };
-            for count in 0..=max_count {
-                next_dp[group][count] = 0;
-                if matches!(line[pos], b'#' | b'?') {
-                    next_dp[group][count] += dp[group][count + 1];
-                }
-            }
-            if matches!(line[pos], b'.' | b'?') {
-                next_dp[group][0] += dp[group][0];
-                if group < m {
-                    next_dp[group][max_count] += dp[group + 1][0];
-                }
-            }
-        }
-        std::mem::swap(&mut dp, &mut next_dp);
+fn total_combinations(input: &[&str]) -> (usize, MemoStats) {
+    sum_arrangements(input.iter().map(|line| {
+        let (conditions, counts) = parse_record(line);
+        (conditions.to_string(), counts)
+    }))
+}
+
+// The arrangements of every record added up, along with the cache statistics of all of them.
+fn sum_arrangements<I>(records: I) -> (usize, MemoStats)
+where
+    I: Iterator<Item = (String, Vec<usize>)>,
+{
+    records.fold(
+        (0, MemoStats::default()),
+        |(total, stats), (conditions, counts)| {
+            let mut memo = Memo::new();
+            let arrangements = count_arrangements(&mut memo, conditions.as_bytes(), &counts);
+
+            (total + arrangements, stats + memo.stats())
+        },
+    )
+}
+
+// The ways the unknown springs in `line` can be filled in to give the damaged groups in
+// `counts`. Within one record a suffix of the line and of the counts is identified by their
+// lengths, so those are the key.
+fn count_arrangements(
+    memo: &mut Memo<(usize, usize), usize>,
+    line: &[u8],
+    counts: &[usize],
+) -> usize {
+    let Some(&group) = counts.first() else {
+        return usize::from(!line.contains(&b'#'));
+    };
+    if line.len() < group {
+        return 0;
     }
 
-    dp[0][0]
+    memo.get_or_insert_with(&(line.len(), counts.len()), |memo| {
+        let mut arrangements = 0;
+
+        if line[0] != b'#' {
+            arrangements += count_arrangements(memo, &line[1..], counts);
+        }
+
+        // the group starts here, it has to fit before the next operational spring and can't
+        // run straight into another damaged one
+        if line[0] != b'.' && !line[..group].contains(&b'.') && line.get(group) != Some(&b'#') {
+            let rest = line.get(group + 1..).unwrap_or_default();
+            arrangements += count_arrangements(memo, rest, &counts[1..]);
+        }
+
+        arrangements
+    })
 }
 
 #[cfg(test)]
@@ -94,7 +104,7 @@ mod tests {
         ];
 
         let expected = 21;
-        let actual = total_combinations(&input);
+        let (actual, _) = total_combinations(&input);
         assert_eq!(actual, expected);
     }
 
@@ -111,7 +121,9 @@ mod tests {
         ];
 
         let expected = 525152;
-        let actual = total_combinations_unfolded(&input);
+        let (actual, stats) = total_combinations_unfolded(&input);
         assert_eq!(actual, expected);
+        assert!(stats.hits > 0);
+        assert_eq!(stats.size, stats.misses);
     }
 }
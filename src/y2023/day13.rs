@@ -132,25 +132,11 @@ fn horizontal_reflection(pattern: &[&str]) -> usize {
 }
 
 fn rotate(pattern: &[&str]) -> Vec<String> {
-    let width = pattern.len();
-    let height = pattern[0].len();
+    let mut grid = Grid::parse_char(pattern);
 
-    let mut rotated = Grid::new(width, height, 'O');
+    grid.rotate_clockwise();
 
-    for (y, line) in pattern.iter().enumerate() {
-        for (x, ch) in line.chars().enumerate() {
-            let rx = width - y - 1;
-            let ry = x;
-            rotated[(rx, ry)] = ch;
-        }
-    }
-
-    let mut lines = vec![];
-    for y in 0..height {
-        lines.push(String::from_iter(&rotated[y * width..width + y * width]));
-    }
-
-    lines
+    grid.rows().map(String::from_iter).collect()
 }
 
 #[cfg(test)]
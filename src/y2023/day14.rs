@@ -1,5 +1,4 @@
-use std::{char, collections::HashMap};
-
+use crate::util::cycle::state_at;
 use crate::util::grid::{Grid, GridDirection, Point};
 
 pub fn solve(input: &[&str]) -> String {
@@ -18,38 +17,25 @@ fn calculate_load_once(input: &[&str]) -> usize {
 }
 
 fn calculate_load_spin(input: &[&str]) -> usize {
-    let mut platform = Grid::parse_char(input);
-
-    let mut seen: Option<HashMap<Grid<char>, usize>> = Some(HashMap::new());
+    let platform = Grid::parse_char(input);
 
     let iterations = 1_000_000_000;
 
-    let mut i = 0;
-    while i < iterations {
-        for _ in 0..4 {
-            tilt(&mut platform);
+    let platform = state_at(platform, spin, iterations);
 
-            platform.rotate_clockwise();
-        }
-
-        i += 1;
+    calculate_load(&platform)
+}
 
-        if let Some(ref mut platforms) = seen {
-            if let Some(n) = platforms.get(&platform) {
-                let cycle_start = n;
-                let cycle_end = i;
-                let cycle_len = cycle_end - cycle_start;
+fn spin(platform: &Grid<char>) -> Grid<char> {
+    let mut platform = platform.clone();
 
-                i += ((iterations - i) / cycle_len) * cycle_len;
+    for _ in 0..4 {
+        tilt(&mut platform);
 
-                seen = None;
-            } else {
-                platforms.insert(platform.clone(), i);
-            }
-        }
+        platform.rotate_clockwise();
     }
 
-    calculate_load(&platform)
+    platform
 }
 
 fn calculate_load(platform: &Grid<char>) -> usize {
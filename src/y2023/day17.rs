@@ -1,13 +1,13 @@
 use std::{
     collections::{BinaryHeap, HashMap},
     hash::Hash,
-    io::stdout,
-    thread,
-    time::Duration,
     usize,
 };
 
+#[cfg(feature = "visualize")]
 use log_update::LogUpdate;
+#[cfg(feature = "visualize")]
+use std::{io::stdout, thread, time::Duration};
 
 use crate::util::grid::{Grid, GridDirection, Point};
 
@@ -54,28 +54,37 @@ fn min_path(weights: &Grid<usize>) -> Vec<Crucible> {
     let mut open = BinaryHeap::new();
     open.push(crucible);
 
+    #[cfg(feature = "visualize")]
     let mut log_update = LogUpdate::new(stdout()).unwrap();
     while let Some(q) = open.pop() {
         // dbg!(&open);
         // dbg!(&q);
 
-        let mut debug_grid = Grid::new(weights.width, weights.height, '.');
-        debug_grid[(0, 0)] = 'S';
-        debug_grid[&goal] = 'G';
-        for (c, _) in came_from.iter() {
-            debug_grid[&c.position] = match c.direction {
-                GridDirection::Up => '^',
-                GridDirection::Down => 'v',
-                GridDirection::Left => '<',
-                GridDirection::Right => '>',
-                _ => unreachable!(),
-            };
-        }
-        debug_grid[&q.position] = '#';
+        #[cfg(feature = "visualize")]
+        let mut debug_grid = {
+            let mut debug_grid = Grid::new(weights.width, weights.height, '.');
+            debug_grid[(0, 0)] = 'S';
+            debug_grid[&goal] = 'G';
+            for (c, _) in came_from.iter() {
+                debug_grid[&c.position] = match c.direction {
+                    GridDirection::Up => '^',
+                    GridDirection::Down => 'v',
+                    GridDirection::Left => '<',
+                    GridDirection::Right => '>',
+                    _ => unreachable!(),
+                };
+            }
+            debug_grid[&q.position] = '#';
+
+            debug_grid
+        };
 
         for next in q.possible_moves(weights) {
             // dbg!(&next);
-            debug_grid[&next.position] = 'N';
+            #[cfg(feature = "visualize")]
+            {
+                debug_grid[&next.position] = 'N';
+            }
 
             // let c = costs.get(&next.position);
             // dbg!(c);
@@ -106,8 +115,11 @@ fn min_path(weights: &Grid<usize>) -> Vec<Crucible> {
             open.push(next);
         }
 
-        log_update.render(&format!("{debug_grid}")).unwrap();
-        thread::sleep(Duration::from_millis(1000 / 10));
+        #[cfg(feature = "visualize")]
+        {
+            log_update.render(&format!("{debug_grid}")).unwrap();
+            thread::sleep(Duration::from_millis(1000 / 10));
+        }
         // thread::sleep(Duration::from_millis(1000));
     }
 
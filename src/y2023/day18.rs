@@ -1,6 +1,8 @@
 use regex::Regex;
 
+use crate::util::geometry::lattice_points;
 use crate::util::grid::GridDirection;
+use crate::util::point::Point;
 use std::i64;
 
 pub fn solve(input: &[&str]) -> String {
@@ -10,18 +12,6 @@ pub fn solve(input: &[&str]) -> String {
     format!(" Part1: {} \n Part2: {}", part1, part2)
 }
 
-#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
-struct IPoint {
-    pub x: i64,
-    pub y: i64,
-}
-
-impl IPoint {
-    pub fn new(x: i64, y: i64) -> Self {
-        Self { x, y }
-    }
-}
-
 type DigPlan = Vec<(GridDirection, i64)>;
 
 fn dug_area(dig_plan: &[&str]) -> usize {
@@ -43,13 +33,8 @@ fn parse_dig_plan(input: &[&str]) -> DigPlan {
         .map(|line| {
             let captures = parse_re.captures(line).unwrap();
 
-            let direction = match &captures["direction"] {
-                "U" => GridDirection::Up,
-                "D" => GridDirection::Down,
-                "L" => GridDirection::Left,
-                "R" => GridDirection::Right,
-                _ => unreachable!(),
-            };
+            let direction =
+                GridDirection::try_from(captures["direction"].chars().next().unwrap()).unwrap();
             let distance = &captures["distance"];
             let _color = &captures["color_code"];
 
@@ -85,63 +70,17 @@ fn parse_dig_plan2(input: &[&str]) -> DigPlan {
 }
 
 fn calculate_area(dig_plan: DigPlan) -> usize {
-    let mut startpoint = IPoint::new(0, 0);
-    let points = {
-        let mut lines = Vec::new();
-
-        for (direction, distance) in dig_plan {
-            // dbg!(position);
-            // dbg!((direction, distance));
-            let endpoint = match direction {
-                GridDirection::Up => IPoint::new(startpoint.x, startpoint.y - distance),
-
-                GridDirection::Down => IPoint::new(startpoint.x, startpoint.y + distance),
-
-                GridDirection::Left => IPoint::new(startpoint.x - distance, startpoint.y),
-
-                GridDirection::Right => IPoint::new(startpoint.x + distance, startpoint.y),
-                _ => unreachable!(),
-            };
-
-            lines.push(endpoint);
-
-            startpoint = endpoint;
-        }
-        lines
-    };
-
-    // dbg!(&lines);
-
-    let mut xcc = 0;
-    let mut ycc = 0;
-    for i in 1..points.len() - 1 {
-        let curr = points[i];
-        let next = points[i - 1];
-
-        xcc += curr.x * next.y;
-        ycc += curr.y * next.x;
-    }
-
-    // dbg!(xcc);
-    // dbg!(ycc);
-
-    let inside_area = (xcc - ycc).abs() / 2;
-
-    // dbg!(inside_area);
-
-    let perimeter = {
-        let mut p = 0;
-        for i in 1..points.len() {
-            p += (points[i].x - points[i - 1].x).abs() + (points[i].y - points[i - 1].y).abs();
-        }
-        p += (points.last().unwrap().x - points[0].x).abs()
-            + (points.last().unwrap().y - points[0].y).abs();
-        p
-    };
-
-    // dbg!(perimeter);
+    let mut position = Point::new(0, 0);
+    let corners = dig_plan
+        .into_iter()
+        .map(|(direction, distance)| {
+            position = position + direction.delta() * distance;
+            position
+        })
+        .collect::<Vec<_>>();
 
-    (inside_area + (perimeter / 2) + 1) as usize
+    // the trench is one cube wide, so it covers every lattice point on and inside the loop
+    lattice_points(&corners) as usize
 }
 
 #[cfg(test)]
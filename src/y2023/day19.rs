@@ -2,87 +2,104 @@ use std::{collections::HashMap, ops::Range};
 
 use regex::Regex;
 
+use crate::util::intervals::RangeSet;
+
 pub fn solve(input: &[&str]) -> String {
-    let part1 = process_parts(&input);
-    let part2 = "";
+    let part1 = process_parts(input);
+    let part2 = calculate_combinations(input);
 
     format!(" Part1: {} \n Part2: {}", part1, part2)
 }
 
-#[allow(dead_code)]
+// The ratings still possible for a part on its way through the workflows.
 #[derive(Debug, Clone)]
 struct PartRange {
-    x: Range<usize>,
-    m: Range<usize>,
-    a: Range<usize>,
-    s: Range<usize>,
+    x: RangeSet<usize>,
+    m: RangeSet<usize>,
+    a: RangeSet<usize>,
+    s: RangeSet<usize>,
+}
+
+impl PartRange {
+    fn new(ratings: Range<usize>) -> Self {
+        let ratings = RangeSet::from_range(ratings);
+
+        Self {
+            x: ratings.clone(),
+            m: ratings.clone(),
+            a: ratings.clone(),
+            s: ratings,
+        }
+    }
+
+    fn rating_mut(&mut self, class: &Class) -> &mut RangeSet<usize> {
+        match class {
+            Class::X(_) => &mut self.x,
+            Class::M(_) => &mut self.m,
+            Class::A(_) => &mut self.a,
+            Class::S(_) => &mut self.s,
+        }
+    }
+
+    // Splits into the parts that pass the condition and the parts that don't.
+    fn split(mut self, class: &Class, operator: &Operator) -> (Self, Self) {
+        let value = match class {
+            Class::X(value) | Class::M(value) | Class::A(value) | Class::S(value) => *value,
+        };
+
+        let mut passed = self.clone();
+        let (below, above) = match operator {
+            Operator::Lt => self.rating_mut(class).split_at(value),
+            Operator::Gt => self.rating_mut(class).split_at(value + 1),
+        };
+        let (pass, fail) = match operator {
+            Operator::Lt => (below, above),
+            Operator::Gt => (above, below),
+        };
+
+        *passed.rating_mut(class) = pass;
+        *self.rating_mut(class) = fail;
+
+        (passed, self)
+    }
+
+    fn combinations(&self) -> usize {
+        [&self.x, &self.m, &self.a, &self.s]
+            .iter()
+            .map(|ratings| ratings.total_len())
+            .product()
+    }
 }
 
-#[allow(dead_code)]
 fn calculate_combinations(input: &[&str]) -> usize {
     let workflows = parse_workflows(input);
 
-    let ranges = PartRange {
-        x: 1..4000,
-        m: 1..4000,
-        a: 1..4000,
-        s: 1..4000,
-    };
-
-    count_combinations("in", ranges, &workflows)
+    count_combinations("in", PartRange::new(1..4001), &workflows)
 }
 
-#[allow(dead_code)]
 fn count_combinations(
     start: &str,
-    ranges: PartRange,
+    mut ranges: PartRange,
     workflows: &HashMap<&str, Vec<Rule>>,
 ) -> usize {
     let workflow = workflows.get(start).unwrap();
 
     let mut count = 0;
 
-    let mut non_passed_ranges = ranges.clone();
-
     for rule in workflow.iter() {
-        dbg!(&start);
-        dbg!(&non_passed_ranges);
-        let mut passed_ranges = non_passed_ranges.clone();
-
-        if let Some((class, operator)) = &rule.operation {
-            let (pr, npr, value) = match class {
-                Class::X(value) => (&mut passed_ranges.x, &mut non_passed_ranges.x, value),
-                Class::M(value) => (&mut passed_ranges.m, &mut non_passed_ranges.m, value),
-                Class::A(value) => (&mut passed_ranges.a, &mut non_passed_ranges.a, value),
-                Class::S(value) => (&mut passed_ranges.s, &mut non_passed_ranges.s, value),
-            };
-
-            match operator {
-                Operator::Gt => {
-                    pr.start = *value;
-                    npr.end = *value;
-                }
-                Operator::Lt => {
-                    pr.end = *value;
-                    npr.start = *value;
-                }
+        let passed = match &rule.operation {
+            Some((class, operator)) => {
+                let (passed, failed) = ranges.split(class, operator);
+                ranges = failed;
+                passed
             }
-        }
+            None => std::mem::replace(&mut ranges, PartRange::new(0..0)),
+        };
 
         match &rule.result {
-            Action::Accept => {
-                dbg!(&passed_ranges);
-                dbg!("Accept!");
-                count += (passed_ranges.x.end - passed_ranges.x.start)
-                    * (passed_ranges.m.end - passed_ranges.m.start)
-                    * (passed_ranges.a.end - passed_ranges.a.start)
-                    * (passed_ranges.s.end - passed_ranges.s.start);
-            }
-            Action::Reject => {
-                dbg!(&passed_ranges);
-                dbg!("Reject!");
-            }
-            Action::Workflow(key) => count += count_combinations(&key, passed_ranges, workflows),
+            Action::Accept => count += passed.combinations(),
+            Action::Reject => {}
+            Action::Workflow(key) => count += count_combinations(key, passed, workflows),
         }
     }
 
@@ -95,16 +112,12 @@ fn process_parts(input: &[&str]) -> usize {
     let mut accepted = vec![];
 
     for part in parts.iter() {
-        // dbg!(&part);
         let mut workflow = workflows.get("in").unwrap();
         let mut process = true;
 
         while process {
-            // dbg!(&workflow);
             for rule in workflow.iter() {
-                // dbg!(&rule);
                 let action = rule.apply(&part);
-                // dbg!(&action);
                 match action {
                     Some(Action::Accept) => {
                         accepted.push(part);
@@ -331,7 +344,6 @@ mod tests {
     }
 
     #[test]
-    #[ignore = "part 2 not working"]
     fn test_calculate_combinations() {
         #[rustfmt::skip]
         let input = [
@@ -369,7 +381,7 @@ mod tests {
             "{x=787,m=2655,a=1222,s=2876}"
         ];
 
-        let expected = 255744095984001;
+        let expected = 256000000000000;
         let actual = calculate_combinations(&input);
 
         assert_eq!(actual, expected);
@@ -387,7 +399,7 @@ mod tests {
             "{x=787,m=2655,a=1222,s=2876}"
         ];
 
-        let expected = 255744095984001;
+        let expected = 256000000000000;
         let actual = calculate_combinations(&input);
 
         assert_eq!(actual, expected);
@@ -3,7 +3,7 @@ use std::{
     hash::Hash,
 };
 
-use crate::algorithms::least_common_multiple;
+use crate::util::number_theory::least_common_multiple;
 
 pub fn solve(input: &[&str]) -> String {
     let part1 = run_circuit(1000, input);
@@ -4,6 +4,8 @@ use std::{
     vec,
 };
 
+use crate::util::point::Point3;
+
 pub fn solve(input: &[&str]) -> String {
     let part1 = count_removable_blocks(input);
     let part2 = "";
@@ -104,34 +106,19 @@ fn drop_blocks(blocks: &Vec<Line>) -> Vec<Line> {
     dropped_blocks
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
-struct Point3D {
-    x: usize,
-    y: usize,
-    z: usize,
-}
-
-impl Point3D {
-    fn parse_str(input: &str) -> Self {
-        let mut numbers = input.split(',').map(|n| n.parse::<usize>().unwrap());
-
-        Self {
-            x: numbers.next().unwrap(),
-            y: numbers.next().unwrap(),
-            z: numbers.next().unwrap(),
-        }
-    }
-}
-
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 struct Line {
-    start: Point3D,
-    end: Point3D,
+    start: Point3<usize>,
+    end: Point3<usize>,
 }
 
 impl Line {
     fn parse_str(input: &str) -> Self {
-        let mut points = input.split('~').map(Point3D::parse_str);
+        let mut points = input.split('~').map(|point| {
+            point
+                .parse::<Point3<usize>>()
+                .unwrap_or_else(|e| panic!("could not parse `{input}`: {e}"))
+        });
         let mut start = points.next().unwrap();
         let mut end = points.next().unwrap();
         if end.z < start.z {
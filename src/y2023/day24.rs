@@ -1,16 +1,96 @@
-pub fn solve(_input: &[&str]) -> String {
-    let part1 = "";
+use crate::util::lines::{Intersection, Line2, within_box};
+use crate::util::point::{Point, Point3};
+use crate::util::rational::Rational;
+
+pub fn solve(input: &[&str]) -> String {
+    let part1 = count_future_crossings(input, 200_000_000_000_000, 400_000_000_000_000);
     let part2 = "";
 
     format!(" Part1: {} \n Part2: {}", part1, part2)
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Hailstone {
+    position: Point3<i128>,
+    velocity: Point3<i128>,
+}
+
+impl Hailstone {
+    fn parse_str(input: &str) -> Self {
+        let (position, velocity) = input
+            .split_once('@')
+            .unwrap_or_else(|| panic!("missing `@` in `{input}`"));
+
+        let parse = |point: &str| {
+            point
+                .parse::<Point3<i128>>()
+                .unwrap_or_else(|e| panic!("could not parse `{input}`: {e}"))
+        };
+
+        Self {
+            position: parse(position),
+            velocity: parse(velocity),
+        }
+    }
+
+    // The path in the x-y plane, ignoring z.
+    fn path_xy(&self) -> Line2 {
+        Line2::new(
+            Point::new(self.position.x, self.position.y),
+            Point::new(self.velocity.x, self.velocity.y),
+        )
+    }
+}
+
+// Counts the pairs of hailstones whose x-y paths cross inside the test area in the future.
+fn count_future_crossings(input: &[&str], min: i128, max: i128) -> usize {
+    let hailstones = input
+        .iter()
+        .map(|line| Hailstone::parse_str(line))
+        .collect::<Vec<_>>();
+
+    let min = Point::new(Rational::from(min), Rational::from(min));
+    let max = Point::new(Rational::from(max), Rational::from(max));
+
+    let mut count = 0;
+    for i in 0..hailstones.len() {
+        for j in i + 1..hailstones.len() {
+            let a = hailstones[i].path_xy();
+            let b = hailstones[j].path_xy();
+
+            if let Intersection::At { t, u, point } = a.intersect(&b)
+                && t >= Rational::zero()
+                && u >= Rational::zero()
+                && within_box(&point, &min, &max)
+            {
+                count += 1;
+            }
+        }
+    }
+
+    count
+}
+
 #[cfg(test)]
 mod tests {
 
-    #![allow(unused_imports)]
     use super::*;
+    use pretty_assertions::assert_eq;
 
     #[test]
-    fn test_solve() {}
+    fn test_count_future_crossings() {
+        #[rustfmt::skip]
+        let input = [
+            "19, 13, 30 @ -2,  1, -2",
+            "18, 19, 22 @ -1, -1, -2",
+            "20, 25, 34 @ -2, -2, -4",
+            "12, 31, 28 @ -1, -2, -1",
+            "20, 19, 15 @  1, -5, -3",
+        ];
+
+        let expected = 2;
+        let actual = count_future_crossings(&input, 7, 27);
+
+        assert_eq!(actual, expected);
+    }
 }
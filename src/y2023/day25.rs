@@ -1,16 +1,91 @@
-pub fn solve(_input: &[&str]) -> String {
-    let part1 = "";
+use crate::util::graph::Graph;
+
+pub fn solve(input: &[&str]) -> String {
+    let part1 = disconnected_group_sizes(input);
     let part2 = "";
 
     format!(" Part1: {} \n Part2: {}", part1, part2)
 }
 
+// Each line lists a component and some of the components wired to it, wires work both ways.
+fn parse_wiring(input: &[&str]) -> Graph<(), i64> {
+    let mut graph = Graph::new();
+
+    for line in input {
+        let (component, connected) = line
+            .split_once(':')
+            .unwrap_or_else(|| panic!("Parse Error - Could not find ':' in `{}`", line));
+        let component = graph.node_id(component.trim());
+
+        for other in connected.split_whitespace() {
+            let other = graph.node_id(other);
+            graph.add_undirected_edge(component, other, 1);
+        }
+    }
+
+    graph
+}
+
+// Exactly three wires split the components in two. Some component is on the far side from
+// the first, and the flow between those two is the three wires, so try them in turn.
+fn disconnected_group_sizes(input: &[&str]) -> usize {
+    let graph = parse_wiring(input);
+
+    (1..graph.len())
+        .map(|other| graph.min_cut(0, other, |wire| *wire))
+        .find(|cut| cut.weight == 3)
+        .map(|cut| {
+            let (a, b) = cut.partition_sizes();
+            a * b
+        })
+        .unwrap_or_else(|| panic!("Could not find three wires that split the components"))
+}
+
 #[cfg(test)]
 mod tests {
 
-    #![allow(unused_imports)]
     use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[rustfmt::skip]
+    const INPUT: [&str; 13] = [
+        "jqt: rhn xhk nvd",
+        "rsh: frs pzl lsr",
+        "xhk: hfx",
+        "cmg: qnr nvd lhk bvb",
+        "rhn: xhk bvb hfx",
+        "bvb: xhk hfx",
+        "pzl: lsr hfx nvd",
+        "qnr: nvd",
+        "ntq: jqt hfx bvb xhk",
+        "nvd: lhk",
+        "lsr: lhk",
+        "rzs: qnr cmg lsr rsh",
+        "frs: qnr lhk lsr",
+    ];
 
     #[test]
-    fn test_solve() {}
+    fn test_disconnected_group_sizes() {
+        assert_eq!(disconnected_group_sizes(&INPUT), 54);
+    }
+
+    #[test]
+    fn test_cut_wires() {
+        let graph = parse_wiring(&INPUT);
+        let cut = graph.stoer_wagner(|wire| *wire).unwrap();
+
+        let mut wires = cut
+            .edges(&graph)
+            .iter()
+            .map(|(a, b)| {
+                let mut wire = [graph.name(*a), graph.name(*b)];
+                wire.sort();
+                wire.join("/")
+            })
+            .collect::<Vec<_>>();
+        wires.sort();
+
+        assert_eq!(cut.weight, 3);
+        assert_eq!(wires, ["bvb/cmg", "hfx/pzl", "jqt/nvd"]);
+    }
 }
@@ -22,10 +22,7 @@ fn find_part_numbers(input: &[&str]) -> impl Iterator<Item = u32> {
             digit_buff.push(*ch);
 
             if part_symbol_found == false {
-                for val in GridDirection::all()
-                    .iter()
-                    .filter_map(|direction| grid.get_direction(&point, *direction))
-                {
+                for (_, val) in grid.neighbors8(&point) {
                     if *val != '.' && val.is_ascii_punctuation() {
                         part_symbol_found = true;
                     }
@@ -73,10 +70,7 @@ fn find_gear_part_numbers(point: &Point, grid: &Grid<char>) -> Option<[u32; 2]>
     let mut visited = Grid::new(grid.width, grid.height, false);
     let mut part_numbers = vec![];
 
-    for (point, _) in GridDirection::all()
-        .iter()
-        .filter_map(|direction| grid.enumerate_direction(&point, *direction))
-    {
+    for (point, _) in grid.neighbors8(point) {
         if let Some(number) = find_number(&point, &grid, &mut visited) {
             part_numbers.push(number);
         }
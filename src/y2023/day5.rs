@@ -2,6 +2,8 @@ use std::ops::Range;
 
 use anyhow::{Context, Result};
 
+use crate::util::intervals::RangeSet;
+
 pub fn solve(input: &[&str]) -> String {
     let almanac = Almanac::parse(input).unwrap();
 
@@ -95,6 +97,14 @@ impl AlmanacMap {
         value
     }
 
+    pub fn get_ranges(&self, ranges: &RangeSet<isize>) -> RangeSet<isize> {
+        ranges.map_offsets(
+            self.mappings
+                .iter()
+                .map(|mapping| (mapping.source_range.clone(), mapping.range_offset)),
+        )
+    }
+
     pub fn get_reverse(&self, value: isize) -> isize {
         for mapping in self.mappings.iter() {
             if let Some(mapped_value) = mapping.get_reverse(value) {
@@ -189,61 +199,21 @@ impl Almanac {
     }
 
     pub fn find_lowest_location_seed_ranges(&self) -> isize {
-        dbg!(&self.seed_ranges);
-
-        let locations = {
-            let mut l = self
-                .seed_ranges
-                .iter()
-                .map(|range| self.find_location_for_seed(range.start))
-                .collect::<Vec<_>>();
-            l.sort();
-            l
-        };
-
-        locations[0]
-
-        // let mut location_ranges = {
-        //     let mut destination_mappings = self.humidity_to_location_map.destination_mappings();
-        //     destination_mappings.sort_by(|a, b| a.start.partial_cmp(&b.start).unwrap());
-        //
-        //     let mut location_ranges = vec![];
-        //     let chunks = destination_mappings[..].chunks_exact(2);
-        //     for chunk in chunks {
-        //         let a = &chunk[0];
-        //         let b = &chunk[1];
-        //
-        //         let r = a.start + a.len() as isize..b.start;
-        //
-        //         location_ranges.extend([a.clone(), r, b.clone()]);
-        //     }
-        //
-        //     if let Some(range) = chunks.remainder().get(0) {
-        //         let r = range.start + range.len() as isize..isize::MAX;
-        //
-        //         location_ranges.extend([range.clone(), r]);
-        //     }
-        //
-        //     location_ranges
-        // };
-        //
-        // loop {
-        //     for range in location_ranges.iter_mut() {
-        //         if let Some(location) = range.next() {
-        //             // dbg!(location);
-        //             if let Some(_seed) = self.find_seed_for_location(location) {
-        //                 return location;
-        //             }
-        //         }
-        //
-        //     }
-        //
-        //     if location_ranges.iter().fold(true, |acc, range| acc && !range.is_empty()) == false {
-        //         break;
-        //     }
-        // }
-
-        // 0
+        let seeds = RangeSet::from_ranges(self.seed_ranges.iter().cloned());
+
+        let locations = [
+            &self.seed_to_soil_map,
+            &self.soil_to_fertilizer_map,
+            &self.fertilizer_to_water_map,
+            &self.water_to_light_map,
+            &self.light_to_temperature_map,
+            &self.temperature_to_humidity_map,
+            &self.humidity_to_location_map,
+        ]
+        .iter()
+        .fold(seeds, |ranges, map| map.get_ranges(&ranges));
+
+        locations.min().unwrap()
     }
 
     fn find_location_for_seed(&self, seed: isize) -> isize {
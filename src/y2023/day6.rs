@@ -1,5 +1,7 @@
 use std::{ops::Range, usize};
 
+use crate::util::number_theory::integer_sqrt;
+
 pub fn solve(input: &[&str]) -> String {
     let part1 = record_beating(&Race::parse_races(input));
     let part2 = record_beating(&vec![Race::parse_race(input)]);
@@ -58,33 +60,31 @@ impl Race {
     }
 
     fn beating_count(&self) -> usize {
-        calculate_bounds(self.time as f64, self.distance as f64).len()
+        calculate_bounds(self.time, self.distance).len()
     }
 }
 
-fn calculate_bounds(time: f64, distance: f64) -> Range<usize> {
-    // use quadratic equation to find upper and lower solutions to the current record
-    let lower_bound = ((-1.0 * time + (time * time - 4.0 * distance).powf(0.5)) / -2.0).ceil();
-    let upper_bound = ((-1.0 * time - (time * time - 4.0 * distance).powf(0.5)) / -2.0).floor();
-
-    // move upper and lower bounds so they beat (don't overlap with) the current record
-    let lower = {
-        let mut lower = lower_bound;
-        while lower * (time - lower) <= distance {
-            lower += 1.0;
-        }
+fn calculate_bounds(time: usize, distance: usize) -> Range<usize> {
+    // holding for h beats the record when h * (time - h) > distance, so the bounds are
+    // between the roots (time +- sqrt(time^2 - 4 * distance)) / 2
+    let beats = |hold: usize| hold * (time - hold) > distance;
 
-        lower as usize
+    let Some(discriminant) = (time * time).checked_sub(4 * distance) else {
+        return 0..0;
     };
+    let root = integer_sqrt(discriminant as u64) as usize;
 
-    let upper = {
-        let mut upper = upper_bound;
-        while upper * (time - upper) <= distance {
-            upper -= 1.0;
-        }
+    // the integer square root rounds down, so these start at or before the first and last
+    // winning hold times and only need to be moved inwards
+    let mut lower = (time - root) / 2;
+    while lower <= time && !beats(lower) {
+        lower += 1;
+    }
 
-        upper as usize
-    };
+    let mut upper = (time + root) / 2;
+    while upper > lower && !beats(upper) {
+        upper -= 1;
+    }
 
     lower..upper + 1
 }
@@ -96,24 +96,24 @@ mod tests {
 
     #[test]
     fn test_calculate_bounds() {
-        let time = 7.0;
-        let distance = 9.0;
+        let time = 7;
+        let distance = 9;
 
         let expected = 4;
         let bounds = calculate_bounds(time, distance);
         dbg!(&bounds);
         assert_eq!(bounds.len(), expected);
 
-        let time = 15.0;
-        let distance = 40.0;
+        let time = 15;
+        let distance = 40;
 
         let expected = 8;
         let bounds = calculate_bounds(time, distance);
         dbg!(&bounds);
         assert_eq!(bounds.len(), expected);
 
-        let time = 30.0;
-        let distance = 200.0;
+        let time = 30;
+        let distance = 200;
 
         let expected = 9;
         let bounds = calculate_bounds(time, distance);
@@ -1,12 +1,9 @@
-use std::{
-    cmp::{max, min},
-    collections::HashMap,
-    convert::TryFrom,
-    iter::FromIterator,
-};
+use std::{collections::HashMap, convert::TryFrom, iter::FromIterator};
 
 use anyhow::anyhow;
 
+use crate::util::number_theory::least_common_multiple;
+
 pub fn solve(input: &[&str]) -> String {
     let part1 = num_steps(input);
     let part2 = simultaneous_steps(input);
@@ -66,24 +63,6 @@ fn simultaneous_steps(input: &[&str]) -> usize {
         .fold(1, |lcm, ghost| least_common_multiple(lcm, ghost.steps))
 }
 
-fn least_common_multiple(a: usize, b: usize) -> usize {
-    a * b / greatest_common_denominator(a, b)
-}
-
-fn greatest_common_denominator(a: usize, b: usize) -> usize {
-    let (mut min, mut max) = (min(a, b), max(a, b));
-
-    loop {
-        let res = max % min;
-        if res == 0 {
-            return min;
-        }
-
-        max = min;
-        min = res;
-    }
-}
-
 #[derive(Debug)]
 struct Ghost<'a> {
     current_node: &'a Node<'a>,
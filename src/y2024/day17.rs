@@ -1,3 +1,11 @@
+use anyhow::{Result, anyhow};
+use std::collections::BTreeSet;
+use std::fmt;
+
+use crate::util::vm::disassemble::{Disassemble, Listing};
+use crate::util::vm::symbolic::{Expr, Symbolic, SymbolicFlow, SymbolicMachine, SymbolicStop};
+use crate::util::vm::{Flow, InstructionSet, Machine};
+
 pub fn solve(input: &[&str]) -> String {
     let part1 = solve_part_1(input);
     let part2 = solve_part_2(input);
@@ -5,26 +13,364 @@ pub fn solve(input: &[&str]) -> String {
     format!(" Part1: {} \n Part2: {}", part1, part2)
 }
 
-fn solve_part_1(input: &[&str]) -> usize {
-    input.len()
+// A combo operand, 0 to 3 are themselves and 4 to 6 read registers A to C.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+enum Combo {
+    Literal(u64),
+    Register(usize),
+}
+
+impl Combo {
+    fn value(self, registers: &[u64]) -> u64 {
+        match self {
+            Self::Literal(value) => value,
+            Self::Register(r) => registers[r],
+        }
+    }
+
+    fn expr(self, registers: &[Expr]) -> Expr {
+        match self {
+            Self::Literal(value) => Expr::Constant(value as i64),
+            Self::Register(r) => registers[r].clone(),
+        }
+    }
+
+    // The operand as written in the program.
+    fn word(self) -> u64 {
+        match self {
+            Self::Literal(value) => value,
+            Self::Register(r) => r as u64 + 4,
+        }
+    }
+}
+
+impl fmt::Display for Combo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Literal(value) => write!(f, "{value}"),
+            Self::Register(r) => write!(f, "{}", (b'a' + *r as u8) as char),
+        }
+    }
+}
+
+impl TryFrom<u8> for Combo {
+    type Error = anyhow::Error;
+
+    fn try_from(operand: u8) -> Result<Self> {
+        match operand {
+            0..=3 => Ok(Self::Literal(operand as u64)),
+            4..=6 => Ok(Self::Register(operand as usize - 4)),
+            _ => Err(anyhow!("`{}` is not a valid combo operand", operand)),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+enum Instruction {
+    Adv(Combo),
+    Bxl(u64),
+    Bst(Combo),
+    // jumps to an instruction, the puzzle counts in words and there are two per instruction
+    Jnz(usize),
+    Bxc,
+    Out(Combo),
+    Bdv(Combo),
+    Cdv(Combo),
+}
+
+const A: usize = 0;
+const B: usize = 1;
+const C: usize = 2;
+
+impl InstructionSet for Instruction {
+    type Word = u64;
+    const REGISTERS: usize = 3;
+
+    fn execute(&self, registers: &mut [u64], output: &mut Vec<u64>) -> Result<Flow> {
+        let divide = |registers: &[u64], combo: Combo| {
+            registers[A]
+                .checked_shr(combo.value(registers).try_into().unwrap_or(u32::MAX))
+                .unwrap_or(0)
+        };
+
+        match *self {
+            Self::Adv(combo) => registers[A] = divide(registers, combo),
+            Self::Bxl(literal) => registers[B] ^= literal,
+            Self::Bst(combo) => registers[B] = combo.value(registers) % 8,
+            Self::Jnz(target) if registers[A] != 0 => return Ok(Flow::Goto(target)),
+            Self::Jnz(_) => {}
+            Self::Bxc => registers[B] ^= registers[C],
+            Self::Out(combo) => output.push(combo.value(registers) % 8),
+            Self::Bdv(combo) => registers[B] = divide(registers, combo),
+            Self::Cdv(combo) => registers[C] = divide(registers, combo),
+        }
+
+        Ok(Flow::Next)
+    }
+}
+
+impl Symbolic for Instruction {
+    fn execute_symbolic(
+        &self,
+        registers: &mut [Expr],
+        output: &mut Vec<Expr>,
+    ) -> Result<SymbolicFlow> {
+        let divide =
+            |registers: &[Expr], combo: Combo| registers[A].clone() >> combo.expr(registers);
+
+        match *self {
+            Self::Adv(combo) => registers[A] = divide(registers, combo),
+            Self::Bxl(literal) => registers[B] = registers[B].clone() ^ literal as i64,
+            Self::Bst(combo) => registers[B] = combo.expr(registers) % 8,
+            Self::Jnz(target) => {
+                return Ok(SymbolicFlow::Branch {
+                    condition: registers[A].clone(),
+                    taken: Flow::Goto(target),
+                });
+            }
+            Self::Bxc => registers[B] = registers[B].clone() ^ registers[C].clone(),
+            Self::Out(combo) => output.push(combo.expr(registers) % 8),
+            Self::Bdv(combo) => registers[B] = divide(registers, combo),
+            Self::Cdv(combo) => registers[C] = divide(registers, combo),
+        }
+
+        Ok(SymbolicFlow::Flow(Flow::Next))
+    }
+}
+
+impl Disassemble for Instruction {
+    fn jumps(&self) -> Vec<Flow> {
+        match self {
+            Self::Jnz(target) => vec![Flow::Goto(*target)],
+            _ => vec![],
+        }
+    }
+
+    fn describe(&self) -> String {
+        match self {
+            Self::Adv(combo) => format!("a = a >> {combo}"),
+            Self::Bxl(literal) => format!("b = b ^ {literal}"),
+            Self::Bst(combo) => format!("b = {combo} % 8"),
+            Self::Jnz(target) => format!("if a != 0 jump to {}", target * 2),
+            Self::Bxc => "b = b ^ c".to_string(),
+            Self::Out(combo) => format!("out {combo} % 8"),
+            Self::Bdv(combo) => format!("b = a >> {combo}"),
+            Self::Cdv(combo) => format!("c = a >> {combo}"),
+        }
+    }
+
+    // The two words the instruction was written as.
+    fn mnemonic(&self) -> String {
+        let (opcode, operand) = match self {
+            Self::Adv(combo) => ("adv", combo.word()),
+            Self::Bxl(literal) => ("bxl", *literal),
+            Self::Bst(combo) => ("bst", combo.word()),
+            Self::Jnz(target) => ("jnz", *target as u64 * 2),
+            Self::Bxc => ("bxc", 0),
+            Self::Out(combo) => ("out", combo.word()),
+            Self::Bdv(combo) => ("bdv", combo.word()),
+            Self::Cdv(combo) => ("cdv", combo.word()),
+        };
+
+        format!("{opcode} {operand}")
+    }
+}
+
+impl TryFrom<(u8, u8)> for Instruction {
+    type Error = anyhow::Error;
+
+    fn try_from((opcode, operand): (u8, u8)) -> Result<Self> {
+        match opcode {
+            0 => Ok(Self::Adv(Combo::try_from(operand)?)),
+            1 => Ok(Self::Bxl(operand as u64)),
+            2 => Ok(Self::Bst(Combo::try_from(operand)?)),
+            3 if operand % 2 == 0 => Ok(Self::Jnz(operand as usize / 2)),
+            3 => Err(anyhow!(
+                "Can't jump into the middle of an instruction at {}",
+                operand
+            )),
+            4 => Ok(Self::Bxc),
+            5 => Ok(Self::Out(Combo::try_from(operand)?)),
+            6 => Ok(Self::Bdv(Combo::try_from(operand)?)),
+            7 => Ok(Self::Cdv(Combo::try_from(operand)?)),
+            _ => Err(anyhow!("Unknown opcode `{}`", opcode)),
+        }
+    }
+}
+
+struct Debugger {
+    registers: [u64; 3],
+    // the raw 3-bit words, which part 2 wants the program to print
+    words: Vec<u64>,
+    program: Vec<Instruction>,
+}
+
+fn parse_debugger(input: &[&str]) -> Debugger {
+    let mut registers = [0; 3];
+    let mut words = vec![];
+
+    for line in input.iter().filter(|line| !line.trim().is_empty()) {
+        let (name, value) = line
+            .split_once(':')
+            .unwrap_or_else(|| panic!("Parse Error - Could not find ':' in `{}`", line));
+
+        match name.trim() {
+            "Register A" | "Register B" | "Register C" => {
+                let r = (name.trim().as_bytes()[9] - b'A') as usize;
+                registers[r] = value.trim().parse().unwrap_or_else(|e| {
+                    panic!("Parse Error - Could not parse `{}` as u64: {}", value, e)
+                });
+            }
+            "Program" => {
+                words = value
+                    .trim()
+                    .split(',')
+                    .map(|word| match word.parse::<u8>() {
+                        Ok(word) if word < 8 => word as u64,
+                        _ => panic!("Parse Error - `{}` is not a 3-bit number", word),
+                    })
+                    .collect();
+            }
+            _ => panic!("Parse Error - Unexpected line `{}`", line),
+        }
+    }
+
+    let program = words
+        .chunks(2)
+        .map(|pair| match pair {
+            [opcode, operand] => Instruction::try_from((*opcode as u8, *operand as u8)),
+            _ => Err(anyhow!("The program has an opcode without an operand")),
+        })
+        .collect::<Result<Vec<_>>>()
+        .unwrap_or_else(|e| panic!("Parse Error - {e}"));
+
+    Debugger {
+        registers,
+        words,
+        program,
+    }
+}
+
+fn run(program: &[Instruction], registers: &[u64]) -> Vec<u64> {
+    let mut computer = Machine::new(program.to_vec()).with_registers(registers);
+    computer.run().unwrap_or_else(|e| panic!("{e}"));
+
+    computer.output
+}
+
+fn solve_part_1(input: &[&str]) -> String {
+    let debugger = parse_debugger(input);
+
+    run(&debugger.program, &debugger.registers)
+        .iter()
+        .map(|value| value.to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+// What one pass of the program's loop prints, worked out symbolically from the value of A at
+// the top of the loop. Panics unless the program is a loop that prints one value from A alone
+// and then shifts A down three bits, which every version of the puzzle is.
+fn loop_output(program: &[Instruction]) -> Expr {
+    let mut body = SymbolicMachine::new(program);
+    let stop = body.run(program.len()).unwrap_or_else(|e| panic!("{e}"));
+
+    let SymbolicStop::Branch { pc, .. } = stop else {
+        panic!("Expected the program to loop, it stopped with {stop:?}");
+    };
+    let [printed] = body.output.as_slice() else {
+        panic!("Expected one output each loop, got {:?}", body.output);
+    };
+
+    if pc != program.len() - 1
+        || body.registers[A] != Expr::Register(A) >> 3
+        || printed.registers() != BTreeSet::from([A])
+    {
+        panic!(
+            "Expected a loop that prints from A alone and shifts it by 3, got:\n{}",
+            Listing::new(program)
+        );
+    }
+
+    printed.clone()
 }
 
-fn solve_part_2(input: &[&str]) -> usize {
-    input.len()
+// The lowest value of A that makes the program print itself. Each loop prints something of A
+// and then drops its bottom three bits, so the last output only depends on the top three bits
+// of A. Build A up three bits at a time, matching the output from the end backwards.
+fn solve_part_2(input: &[&str]) -> u64 {
+    let debugger = parse_debugger(input);
+    let printed = loop_output(&debugger.program);
+    let mut candidates = vec![0];
+
+    for word in debugger.words.iter().rev() {
+        candidates = candidates
+            .iter()
+            .flat_map(|a| (0..8).map(move |bits| a * 8 + bits))
+            .filter(|a| *a != 0 && printed.evaluate(&[*a as i64]) == Some(*word as i64))
+            .collect();
+    }
+
+    let [_, b, c] = debugger.registers;
+    candidates
+        .into_iter()
+        .filter(|a| run(&debugger.program, &[*a, b, c]) == debugger.words)
+        .min()
+        .unwrap_or_else(|| panic!("No value of A makes the program print itself"))
 }
 
 #[cfg(test)]
 mod tests {
 
     use super::*;
+    use crate::util::vm::disassemble::Loop;
     use pretty_assertions::assert_eq;
 
+    #[test]
+    fn test_instructions() {
+        // (registers, program, registers after, output)
+        type Case = ([u64; 3], &'static [u8], [u64; 3], &'static [u64]);
+        let cases: [Case; 5] = [
+            ([0, 0, 9], &[2, 6], [0, 1, 9], &[]),
+            ([10, 0, 0], &[5, 0, 5, 1, 5, 4], [10, 0, 0], &[0, 1, 2]),
+            ([0, 29, 0], &[1, 7], [0, 26, 0], &[]),
+            ([0, 2024, 43690], &[4, 0], [0, 44354, 43690], &[]),
+            (
+                [2024, 0, 0],
+                &[0, 1, 5, 4, 3, 0],
+                [0, 0, 0],
+                &[4, 2, 5, 6, 7, 7, 7, 7, 3, 1, 0],
+            ),
+        ];
+
+        for (registers, words, expected_registers, expected_output) in cases {
+            let program = words
+                .chunks(2)
+                .map(|pair| Instruction::try_from((pair[0], pair[1])).unwrap())
+                .collect();
+            let mut computer = Machine::new(program).with_registers(&registers);
+            computer.run().unwrap();
+
+            assert_eq!(computer.registers, expected_registers, "{words:?}");
+            assert_eq!(computer.output, expected_output, "{words:?}");
+        }
+
+        assert!(Instruction::try_from((0, 7)).is_err());
+        assert!(Instruction::try_from((3, 1)).is_err());
+    }
+
     #[test]
     fn test_solve_part_1() {
         #[rustfmt::skip]
-      let input = [];
+        let input = [
+            "Register A: 729",
+            "Register B: 0",
+            "Register C: 0",
+            "",
+            "Program: 0,1,5,4,3,0",
+        ];
 
-        let expected = 0;
+        let expected = "4,6,3,5,6,3,5,2,1,0";
 
         let actual = solve_part_1(&input);
 
@@ -34,12 +380,64 @@ mod tests {
     #[test]
     fn test_solve_part_2() {
         #[rustfmt::skip]
-      let input = [];
+        let input = [
+            "Register A: 2024",
+            "Register B: 0",
+            "Register C: 0",
+            "",
+            "Program: 0,3,5,4,3,0",
+        ];
 
-        let expected = 0;
+        let expected = 117440;
 
         let actual = solve_part_2(&input);
 
         assert_eq!(actual, expected);
     }
+
+    #[rustfmt::skip]
+    const QUINE: [&str; 5] = [
+        "Register A: 0",
+        "Register B: 0",
+        "Register C: 0",
+        "",
+        "Program: 2,4,1,1,7,5,1,5,4,0,5,5,0,3,3,0",
+    ];
+
+    #[test]
+    fn test_disassemble() {
+        let debugger = parse_debugger(&QUINE);
+        let listing = Listing::new(&debugger.program);
+
+        #[rustfmt::skip]
+        let expected = [
+            "L0:",
+            "   0  bst 4  b = a % 8",
+            "   1  bxl 1  b = b ^ 1",
+            "   2  cdv 5  c = a >> b",
+            "   3  bxl 5  b = b ^ 5",
+            "   4  bxc 0  b = b ^ c",
+            "   5  out 5  out b % 8",
+            "   6  adv 3  a = a >> 3",
+            "   7  jnz 0  if a != 0 jump to 0                 ; -> L0, loop",
+        ];
+        assert_eq!(listing.to_string().lines().collect::<Vec<_>>(), expected);
+        assert_eq!(listing.loops, [Loop { start: 0, end: 7 }]);
+    }
+
+    #[test]
+    fn test_loop_output() {
+        let debugger = parse_debugger(&QUINE);
+        let printed = loop_output(&debugger.program);
+
+        assert_eq!(
+            printed.to_string(),
+            "(((a % 8) ^ 4) ^ (a >> ((a % 8) ^ 1))) % 8"
+        );
+
+        for a in [1, 7, 8, 12345, 1 << 40] {
+            let output = run(&debugger.program, &[a, 0, 0]);
+            assert_eq!(printed.evaluate(&[a as i64]), Some(output[0] as i64), "{a}");
+        }
+    }
 }
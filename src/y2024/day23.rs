@@ -1,3 +1,5 @@
+use crate::util::graph::Graph;
+
 pub fn solve(input: &[&str]) -> String {
     let part1 = solve_part_1(input);
     let part2 = solve_part_2(input);
@@ -5,12 +7,34 @@ pub fn solve(input: &[&str]) -> String {
     format!(" Part1: {} \n Part2: {}", part1, part2)
 }
 
+// Each line is a pair of connected computers, like `kh-tc`.
+fn parse_network(input: &[&str]) -> Graph {
+    let mut network = Graph::new();
+
+    for line in input {
+        let (a, b) = line
+            .split_once('-')
+            .unwrap_or_else(|| panic!("Parse Error - Could not find '-' in `{}`", line));
+
+        let (a, b) = (network.node_id(a), network.node_id(b));
+        network.add_undirected_edge(a, b, ());
+    }
+
+    network
+}
+
 fn solve_part_1(input: &[&str]) -> usize {
-    input.len()
+    let network = parse_network(input);
+
+    network
+        .triangles_containing(|computer| network.name(computer).starts_with('t'))
+        .len()
 }
 
-fn solve_part_2(input: &[&str]) -> usize {
-    input.len()
+fn solve_part_2(input: &[&str]) -> String {
+    let network = parse_network(input);
+
+    network.canonical_names(&network.maximum_clique())
 }
 
 #[cfg(test)]
@@ -19,26 +43,28 @@ mod tests {
     use super::*;
     use pretty_assertions::assert_eq;
 
+    #[rustfmt::skip]
+    const INPUT: [&str; 32] = [
+        "kh-tc", "qp-kh", "de-cg", "ka-co", "yn-aq", "qp-ub", "cg-tb", "vc-aq",
+        "tb-ka", "wh-tc", "yn-cg", "kh-ub", "ta-co", "de-co", "tc-td", "tb-wq",
+        "wh-td", "ta-ka", "td-qp", "aq-cg", "wq-ub", "ub-vc", "de-ta", "wq-aq",
+        "wq-vc", "wh-yn", "ka-de", "kh-ta", "co-tc", "wh-qp", "tb-vc", "td-yn",
+    ];
+
     #[test]
     fn test_solve_part_1() {
-        #[rustfmt::skip]
-      let input = [];
-
-        let expected = 0;
+        let expected = 7;
 
-        let actual = solve_part_1(&input);
+        let actual = solve_part_1(&INPUT);
 
         assert_eq!(actual, expected);
     }
 
     #[test]
     fn test_solve_part_2() {
-        #[rustfmt::skip]
-      let input = [];
-
-        let expected = 0;
+        let expected = "co,de,ka,ta";
 
-        let actual = solve_part_2(&input);
+        let actual = solve_part_2(&INPUT);
 
         assert_eq!(actual, expected);
     }
@@ -175,17 +175,9 @@ impl Guard {
 }
 
 fn solve_part_1(input: &[&str]) -> usize {
-    let map = Grid::parse_char(input);
-
-    let guard_position = {
-        let (idx, _) = map
-            .iter()
-            .enumerate()
-            .filter(|(_, ch)| **ch == '^')
-            .next()
-            .unwrap();
-        map.idx_point(idx)
-    };
+    let mut map = Grid::parse_char(input);
+
+    let guard_position = map.extract_markers(&['^'], '.')[&'^'][0];
 
     let mut guard = Guard::new(guard_position);
 
@@ -193,17 +185,9 @@ fn solve_part_1(input: &[&str]) -> usize {
 }
 
 fn solve_part_2(input: &[&str]) -> usize {
-    let map = Grid::parse_char(input);
-
-    let guard_position = {
-        let (idx, _) = map
-            .iter()
-            .enumerate()
-            .filter(|(_, ch)| **ch == '^')
-            .next()
-            .unwrap();
-        map.idx_point(idx)
-    };
+    let mut map = Grid::parse_char(input);
+
+    let guard_position = map.extract_markers(&['^'], '.')[&'^'][0];
 
     let mut guard = Guard::new(guard_position);
 
@@ -1,3 +1,5 @@
+use crate::util::combinatorics::cartesian_power;
+
 pub fn solve(input: &[&str]) -> String {
     let part1 = solve_part_1(input);
     let part2 = solve_part_2(input);
@@ -6,57 +8,34 @@ pub fn solve(input: &[&str]) -> String {
 }
 
 fn solve_part_1(input: &[&str]) -> u64 {
-    let mut total = 0;
-
-    for line in input {
-        let (value, rest) = line.split_once(':').unwrap();
-        let value = value.parse::<u64>().unwrap();
-        let operands = rest
-            .split(' ')
-            .skip(1)
-            .map(|o| o.parse::<u64>().unwrap())
-            .collect::<Vec<_>>();
-
-        if can_solve(value, &operands) {
-            total += value;
-        }
-    }
-
-    total
+    total_calibration(input, &[Operator::Add, Operator::Multiply])
 }
 
 fn solve_part_2(input: &[&str]) -> u64 {
-    fn can_solve_2(value: u64, acc: u64, operands: &[u64]) -> bool {
-        // dbg!(acc);
-        // dbg!(&operands);
-        if acc == value && operands.len() == 0 {
-            return true;
-        }
-
-        if acc > value {
-            return false;
-        }
-
-        if let Some(next) = operands.iter().next() {
-            // dbg!("+");
-            if can_solve_2(value, acc + next, &operands[1..]) {
-                return true;
-            }
+    total_calibration(
+        input,
+        &[Operator::Add, Operator::Multiply, Operator::Concatenate],
+    )
+}
 
-            // dbg!("*");
-            if can_solve_2(value, acc * next, &operands[1..]) {
-                return true;
-            };
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operator {
+    Add,
+    Multiply,
+    Concatenate,
+}
 
-            // dbg!("||");
-            if can_solve_2(value, concat_nums(acc, *next), &operands[1..]) {
-                return true;
-            };
+impl Operator {
+    fn apply(&self, a: u64, b: u64) -> u64 {
+        match self {
+            Operator::Add => a + b,
+            Operator::Multiply => a * b,
+            Operator::Concatenate => concat_nums(a, b),
         }
-
-        false
     }
+}
 
+fn total_calibration(input: &[&str], operators: &[Operator]) -> u64 {
     let mut total = 0;
 
     for line in input {
@@ -68,11 +47,7 @@ fn solve_part_2(input: &[&str]) -> u64 {
             .map(|o| o.parse::<u64>().unwrap())
             .collect::<Vec<_>>();
 
-        // dbg!(value);
-        // dbg!(&operands);
-
-        if can_solve_2(value, operands[0], &operands[1..]) {
-            // println!("{value} true");
+        if can_solve(value, &operands, operators) {
             total += value;
         }
     }
@@ -80,23 +55,29 @@ fn solve_part_2(input: &[&str]) -> u64 {
     total
 }
 
-fn can_solve(value: u64, operands: &[u64]) -> bool {
-    can_solve_rec(value, operands[0], &operands[1..])
-}
+// Tries every choice of operators, evaluated left to right. None of the operators can make
+// the total smaller, so once it overshoots every choice sharing that prefix is skipped.
+fn can_solve(value: u64, operands: &[u64], operators: &[Operator]) -> bool {
+    let mut choices = cartesian_power(operators, operands.len() - 1);
 
-fn can_solve_rec(value: u64, acc: u64, operands: &[u64]) -> bool {
-    if acc == value {
-        return true;
-    }
+    while let Some(chosen) = choices.next_slice() {
+        let mut acc = operands[0];
+        let mut overshot = None;
+
+        for (i, (operator, operand)) in chosen.iter().zip(&operands[1..]).enumerate() {
+            acc = operator.apply(acc, *operand);
 
-    if let Some(next) = operands.iter().next() {
-        if can_solve_rec(value, acc + next, &operands[1..]) {
-            return true;
+            if acc > value {
+                overshot = Some(i + 1);
+                break;
+            }
         }
 
-        if can_solve_rec(value, acc * next, &operands[1..]) {
-            return true;
-        };
+        match overshot {
+            Some(prefix) => choices.skip_prefix(prefix),
+            None if acc == value => return true,
+            None => {}
+        }
     }
 
     false
@@ -1,3 +1,7 @@
+use crate::util::modular_aritmatic::Mod;
+
+type Dial = Mod<100>;
+
 pub fn solve(input: &[&str]) -> String {
     let part_1 = solve_part_1(input);
     let part_2 = solve_part_2(input);
@@ -6,8 +10,8 @@ pub fn solve(input: &[&str]) -> String {
 }
 
 fn solve_part_1(input: &[&str]) -> u32 {
-    fn solve(input: &[&str], dial_position: i32) -> u32 {
-        let count = if dial_position % 100 == 0 { 1 } else { 0 };
+    fn solve(input: &[&str], dial_position: Dial) -> u32 {
+        let count = if dial_position.value() == 0 { 1 } else { 0 };
 
         // eprintln!("");
         // dbg!(dial_position);
@@ -27,14 +31,14 @@ fn solve_part_1(input: &[&str]) -> u32 {
             panic!("malformed input `{}`", input[0]);
         };
 
-        count + solve(&input[1..], dial_position + n)
+        count + solve(&input[1..], dial_position + Dial::new(n))
     }
 
-    solve(input, 50)
+    solve(input, Dial::new(50))
 }
 
 fn solve_part_2(input: &[&str]) -> i32 {
-    let mut position = 50;
+    let mut position = Dial::new(50);
     let mut zero_count = 0;
 
     for line in input {
@@ -49,13 +53,14 @@ fn solve_part_2(input: &[&str]) -> i32 {
             panic!("malformed input `{}`", input[0]);
         };
 
+        let offset = position.value() as i32;
         if distance >= 0 {
-            zero_count += (position + distance) / 100;
+            zero_count += (offset + distance) / 100;
         } else {
-            zero_count += ((100 - position) % 100 + distance.abs()) / 100;
+            zero_count += ((100 - offset) % 100 + distance.abs()) / 100;
         }
 
-        position = (position + distance).rem_euclid(100);
+        position += Dial::new(distance);
     }
 
     zero_count
@@ -1,6 +1,7 @@
 use core::panic;
 use std::collections::{HashSet, VecDeque};
-use z3::{Optimize, SatResult, ast::Int};
+
+use crate::util::ilp::IntegerProgram;
 
 pub fn solve(input: &[&str]) -> String {
     let machines = {
@@ -62,7 +63,40 @@ impl Machine {
         panic!("Could not find solution for {self:#?}")
     }
 
+    // One variable per button, each joltage counter gives an equality over the buttons that
+    // touch it. No button can be pressed more often than the smallest counter it touches.
     fn configure_joltage(&self) -> u64 {
+        let buttons = &self.button_indexes;
+
+        let mut program = IntegerProgram::new(buttons.len()).minimise(&vec![1; buttons.len()]);
+
+        for (pos, &target) in self.target_joltages.iter().enumerate() {
+            let coefficients = buttons
+                .iter()
+                .map(|btn| btn.contains(&pos) as i64)
+                .collect::<Vec<_>>();
+
+            program = program.equal(&coefficients, target as i64);
+        }
+
+        for (i, btn) in buttons.iter().enumerate() {
+            let bound = btn.iter().map(|pos| self.target_joltages[*pos]).min();
+            if let Some(bound) = bound {
+                program = program.upper_bound(i, bound as i64);
+            }
+        }
+
+        match program.solve() {
+            Some(solution) => solution.objective as u64,
+            None => panic!("Could not find solution for {self:#?}"),
+        }
+    }
+
+    // The original z3 formulation, kept to cross check the solver above.
+    #[cfg(all(test, feature = "smt"))]
+    fn configure_joltage_z3(&self) -> u64 {
+        use z3::{Optimize, SatResult, ast::Int};
+
         let joltages = &self.target_joltages;
         let buttons = self.button_indexes.clone();
 
@@ -265,6 +299,21 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[cfg(feature = "smt")]
+    #[test]
+    fn test_configure_joltage_matches_z3() {
+        #[rustfmt::skip]
+        let machines = [
+            "[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}",
+            "[...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}",
+            "[.###.#] (0,1,2,3,4) (0,3,4) (0,1,2,4,5) (1,2) {10,11,11,5,10,5}",
+        ].iter().map(|&line| Machine::try_from(line).unwrap()).collect::<Vec<_>>();
+
+        for machine in machines.iter() {
+            assert_eq!(machine.configure_joltage(), machine.configure_joltage_z3());
+        }
+    }
+
     #[test]
     fn test_machine_from_str() {
         let input = "[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}";
@@ -1,5 +1,7 @@
 use std::collections::{HashMap, VecDeque};
 
+use crate::util::graph::Graph;
+
 pub fn solve(input: &[&str]) -> String {
     let part_1 = solve_part_1(input);
     let part_2 = solve_part_2(input);
@@ -7,29 +9,23 @@ pub fn solve(input: &[&str]) -> String {
     format!(" Part1: {part_1} \n Part2: {part_2}")
 }
 
+fn parse_devices(input: &[&str]) -> Graph {
+    Graph::parse_adjacency_lists(input).unwrap_or_else(|e| panic!("Parse Error - {}", e))
+}
+
 fn solve_part_1(input: &[&str]) -> u32 {
-    let graph = input
-        .iter()
-        .map(|line| {
-            let (name, outputs) = line
-                .split_once(':')
-                .unwrap_or_else(|| panic!("Parse Error - Could not find ':' in `{}`", line));
-
-            (name, outputs.split_whitespace().collect::<Vec<_>>())
-        })
-        .collect::<HashMap<_, _>>();
-
-    let mut to_visit = VecDeque::from(["you"]);
-    let mut paths = 0;
+    let graph = parse_devices(input);
+    let out = graph.id("out");
 
-    while !to_visit.is_empty() {
-        let node = to_visit.pop_front().unwrap();
+    let mut to_visit = VecDeque::from([graph.id("you").unwrap()]);
+    let mut paths = 0;
 
-        for &output in graph.get(&node).unwrap().iter() {
-            if output == "out" {
+    while let Some(node) = to_visit.pop_front() {
+        for (output, _) in graph.neighbours(node) {
+            if Some(output) == out {
                 paths += 1;
             } else {
-                to_visit.push_back(&output);
+                to_visit.push_back(output);
             }
         }
     }
@@ -38,44 +34,38 @@ fn solve_part_1(input: &[&str]) -> u32 {
 }
 
 fn solve_part_2(input: &[&str]) -> u32 {
-    let graph = input
-        .iter()
-        .map(|line| {
-            let (name, outputs) = line
-                .split_once(':')
-                .unwrap_or_else(|| panic!("Parse Error - Could not find ':' in `{}`", line));
-
-            (name, outputs.split_whitespace().collect::<Vec<_>>())
-        })
-        .collect::<HashMap<_, _>>();
-
-    fn valid_routes<'a>(
-        position: &'a str,
+    let graph = parse_devices(input);
+
+    struct Devices {
+        out: Option<usize>,
+        dac: Option<usize>,
+        fft: Option<usize>,
+    }
+
+    fn valid_routes(
+        position: usize,
         visited_dac: bool,
         visited_fft: bool,
-        graph: &HashMap<&str, Vec<&'a str>>,
-        memo: &mut HashMap<(&'a str, bool, bool), u32>,
+        graph: &Graph,
+        devices: &Devices,
+        memo: &mut HashMap<(usize, bool, bool), u32>,
     ) -> u32 {
         // dbg!(position);
-        if visited_dac && visited_fft && position == "out" {
+        if visited_dac && visited_fft && Some(position) == devices.out {
             return 1;
         }
 
-        let visited_dac = visited_dac || position == "dac";
-        let visited_fft = visited_fft || position == "fft";
+        let visited_dac = visited_dac || Some(position) == devices.dac;
+        let visited_fft = visited_fft || Some(position) == devices.fft;
 
         if let Some(count) = memo.get(&(position, visited_dac, visited_fft)) {
             return *count;
         }
 
-        let Some(outputs) = graph.get(position) else {
-            return 0;
-        };
-
         let mut count = 0;
 
-        for output in outputs {
-            count += valid_routes(output, visited_dac, visited_fft, graph, memo);
+        for (output, _) in graph.neighbours(position) {
+            count += valid_routes(output, visited_dac, visited_fft, graph, devices, memo);
         }
 
         memo.insert((position, visited_dac, visited_fft), count);
@@ -83,7 +73,20 @@ fn solve_part_2(input: &[&str]) -> u32 {
         count
     }
 
-    valid_routes("svr", false, false, &graph, &mut HashMap::new())
+    let devices = Devices {
+        out: graph.id("out"),
+        dac: graph.id("dac"),
+        fft: graph.id("fft"),
+    };
+
+    valid_routes(
+        graph.id("svr").unwrap(),
+        false,
+        false,
+        &graph,
+        &devices,
+        &mut HashMap::new(),
+    )
 }
 
 #[cfg(test)]
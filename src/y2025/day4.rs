@@ -1,4 +1,4 @@
-use crate::util::grid::{Grid, GridDirection, Point};
+use crate::util::grid::{Grid, Point};
 
 pub fn solve(input: &[&str]) -> String {
     let part_1 = solve_part_1(input);
@@ -19,19 +19,13 @@ fn solve_part_1(input: &[&str]) -> usize {
 
             let point = grid.idx_point(idx);
 
-            let mut adj_count = 0;
+            let adj_count = grid
+                .neighbors8(&point)
+                .filter(|(_, adjacent)| **adjacent == '@')
+                .count();
 
-            for direction in GridDirection::all() {
-                if let Some(adjacent) = grid.get_direction(&point, direction) {
-                    if *adjacent == '@' {
-                        adj_count += 1;
-                    }
-                }
-
-                if adj_count >= 4 {
-                    inaccessible += 1;
-                    break;
-                }
+            if adj_count >= 4 {
+                inaccessible += 1;
             }
         }
     }
@@ -73,19 +67,10 @@ fn find_accessible(grid: &Grid<char>) -> Vec<Point> {
         if *val == '@' {
             let point = grid.idx_point(idx);
 
-            let mut adj_count = 0;
-
-            for direction in GridDirection::all() {
-                if let Some(adjacent) = grid.get_direction(&point, direction) {
-                    if *adjacent == '@' {
-                        adj_count += 1;
-                    }
-                }
-
-                if adj_count >= 4 {
-                    break;
-                }
-            }
+            let adj_count = grid
+                .neighbors8(&point)
+                .filter(|(_, adjacent)| **adjacent == '@')
+                .count();
 
             if adj_count < 4 {
                 accessible.push(point);
@@ -1,6 +1,8 @@
 use core::panic;
 use std::collections::HashSet;
 
+use crate::util::intervals::RangeSet;
+
 pub fn solve(input: &[&str]) -> String {
     let part_1 = solve_part_1(input);
     let part_2 = solve_part_2(input);
@@ -12,27 +14,10 @@ fn solve_part_1(input: &[&str]) -> usize {
     let (ranges, ids) = {
         let mut lines = input.iter();
 
-        let mut ranges = vec![];
-
-        while let Some(line) = lines.next() {
-            if *line == "" {
-                break;
-            }
-
-            let (start, end) = line
-                .split_once('-')
-                .unwrap_or_else(|| panic!("malformed line {}", line));
-            let range = start
-                .parse::<u64>()
-                .unwrap_or_else(|e| panic!("could not parse `{}`: {}", start, e))
-                ..=end
-                    .parse::<u64>()
-                    .unwrap_or_else(|e| panic!("could not parse `{}`: {}", end, e));
-            ranges.push(range);
-        }
+        let ranges = parse_ranges(&mut lines);
 
         let mut ids = HashSet::new();
-        while let Some(line) = lines.next() {
+        for line in lines {
             let id = line
                 .parse::<u64>()
                 .unwrap_or_else(|e| panic!("could not parse `{}`: {e}", line));
@@ -42,88 +27,40 @@ fn solve_part_1(input: &[&str]) -> usize {
         (ranges, ids)
     };
 
-    let mut fresh = 0;
-
-    for id in ids {
-        for range in ranges.iter() {
-            if range.contains(&id) {
-                fresh += 1;
-                break;
-            }
-        }
-    }
-
-    fresh
-}
-
-#[derive(Debug)]
-struct IdRange {
-    start: u64,
-    end: u64,
+    ids.iter().filter(|id| ranges.contains(id)).count()
 }
 
-impl IdRange {
-    fn new(start: u64, end: u64) -> Self {
-        Self { start, end }
-    }
-
-    fn contains(&self, val: u64) -> bool {
-        val >= self.start && val <= self.end
-    }
+fn solve_part_2(input: &[&str]) -> u64 {
+    let ranges = parse_ranges(&mut input.iter());
 
-    fn length(&self) -> u64 {
-        (self.end - self.start) + 1
-    }
+    ranges.total_len()
 }
 
-fn solve_part_2(input: &[&str]) -> u64 {
+fn parse_ranges<'a, I>(lines: &mut I) -> RangeSet<u64>
+where
+    I: Iterator<Item = &'a &'a str>,
+{
     let mut ranges = vec![];
 
-    for line in input {
-        if *line == "" {
+    for line in lines {
+        if line.is_empty() {
             break;
         }
 
         let (start, end) = line
             .split_once('-')
             .unwrap_or_else(|| panic!("malformed line {}", line));
-        let range = IdRange::new(
-            start
-                .parse::<u64>()
-                .unwrap_or_else(|e| panic!("could not parse `{}`: {}", start, e)),
-            end.parse::<u64>()
-                .unwrap_or_else(|e| panic!("could not parse `{}`: {}", end, e)),
-        );
-        ranges.push(range);
-    }
-
-    ranges.sort_by_key(|r| r.start);
-
-    let mut merged_ranges: Vec<IdRange> = vec![];
-
-    for range in ranges {
-        if let Some(last_merged) = merged_ranges.last_mut() {
-            if range.start > last_merged.end + 1 {
-                merged_ranges.push(range);
-            } else if (last_merged.contains(range.start) && !last_merged.contains(range.end))
-                || (range.start == last_merged.end + 1)
-            {
-                last_merged.end = range.end;
-            }
-        } else {
-            merged_ranges.push(range);
-        }
-    }
-
-    // dbg!(&merged_ranges);
-
-    let mut count = 0;
-
-    for range in merged_ranges {
-        count += range.length();
+        let start = start
+            .parse::<u64>()
+            .unwrap_or_else(|e| panic!("could not parse `{}`: {}", start, e));
+        let end = end
+            .parse::<u64>()
+            .unwrap_or_else(|e| panic!("could not parse `{}`: {}", end, e));
+
+        ranges.push(start..end + 1);
     }
 
-    count
+    RangeSet::from_ranges(ranges)
 }
 
 #[cfg(test)]
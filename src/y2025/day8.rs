@@ -1,9 +1,6 @@
 use core::panic;
-use std::{
-    collections::{HashMap, HashSet},
-    usize,
-};
 
+use crate::util::dsu::DisjointSet;
 use crate::util::point::Point3;
 
 pub fn solve(input: &[&str]) -> String {
@@ -14,212 +11,53 @@ pub fn solve(input: &[&str]) -> String {
 }
 
 fn solve_part_1(n: usize, input: &[&str]) -> usize {
-    // parse input into points
-    let points: Vec<Point3> = input
-        .iter()
-        .map(|line| {
-            let mut nums = line.split(',');
-            let x = nums
-                .next()
-                .unwrap_or_else(|| panic!("no x in line: {}", line))
-                .parse::<usize>()
-                .unwrap_or_else(|e| panic!("could not parse x from {}: {}", line, e));
-            let y = nums
-                .next()
-                .unwrap_or_else(|| panic!("no y in line: {}", line))
-                .parse::<usize>()
-                .unwrap_or_else(|e| panic!("could not parse y from {}: {}", line, e));
-            let z = nums
-                .next()
-                .unwrap_or_else(|| panic!("no z in line: {}", line))
-                .parse::<usize>()
-                .unwrap_or_else(|e| panic!("could not parse z from {}: {}", line, e));
-
-            Point3::new(x, y, z)
-        })
-        .collect::<Vec<_>>();
-
-    // calculate distance between all point pairs and sort
-    let distances = {
-        let mut distances = vec![];
-        for i in 0..points.len() {
-            for j in i + 1..points.len() {
-                let distance = points[i].euclidean_distance(points[j]);
-                distances.push((distance, &points[i], &points[j]));
-            }
-        }
-
-        distances.sort_by(|a, b| a.0.total_cmp(&b.0));
-
-        distances
-    };
-
-    // dbg!(&distances);
+    let points = parse_points(input);
+    let mut circuits = DisjointSet::new(points.len());
 
-    // create circuits out of the first n point pairs
-    let mut point_to_circuit: HashMap<&Point3, usize> = HashMap::new();
-    let mut next_circuit_id = 0;
-
-    for i in 0..n {
-        if i >= distances.len() {
-            break;
-        }
-
-        let (_d, a, b) = distances[i];
-
-        if let Some(a_cid) = point_to_circuit.get(a)
-            && let Some(b_cid) = point_to_circuit.get(b)
-        {
-            if *a_cid != *b_cid {
-                let to_merge = b_cid.clone();
-                let merged_cid = a_cid.clone();
-
-                for (_, cid) in point_to_circuit.iter_mut() {
-                    if *cid == to_merge {
-                        *cid = merged_cid;
-                    }
-                }
-            }
-        } else if let Some(cid) = point_to_circuit.get(a) {
-            point_to_circuit.insert(b, *cid);
-        } else if let Some(cid) = point_to_circuit.get(b) {
-            point_to_circuit.insert(a, *cid);
-        } else {
-            point_to_circuit.insert(a, next_circuit_id);
-            point_to_circuit.insert(b, next_circuit_id);
-            next_circuit_id += 1;
-        }
+    // connect the n closest pairs, even if they are already in the same circuit
+    for (a, b) in closest_pairs(&points).into_iter().take(n) {
+        circuits.union(a, b);
     }
 
-    // dbg!(&point_to_circuit);
+    circuits.component_sizes().iter().take(3).product()
+}
 
-    // transform point-to-circuit into circuit-to-point representation
-    let mut circuits: HashMap<usize, Vec<&Point3>> = HashMap::new();
+fn solve_part_2(input: &[&str]) -> i64 {
+    let points = parse_points(input);
+    let mut circuits = DisjointSet::new(points.len());
 
-    for (point, cid) in point_to_circuit {
-        circuits
-            .entry(cid)
-            .and_modify(|points| (*points).push(point))
-            .or_insert(vec![point]);
+    for (a, b) in closest_pairs(&points) {
+        // the last connection is the one that leaves a single circuit
+        if circuits.union(a, b) && circuits.component_count() == 1 {
+            return points[a].x * points[b].x;
+        }
     }
 
-    // dbg!(&circuits);
-
-    // calculate sizes of circuits and sort
-    let circuit_sizes = {
-        let mut sorted_circuits = circuits
-            .iter()
-            .map(|(_cid, points)| points.len())
-            .collect::<Vec<_>>();
-        sorted_circuits.sort_by(|a, b| b.cmp(a));
-        sorted_circuits
-    };
-
-    // dbg!(&circuit_sizes);
-
-    // result
-    circuit_sizes.iter().take(3).product()
+    0
 }
 
-fn solve_part_2(input: &[&str]) -> usize {
-    // parse input into points
-    let points: Vec<Point3> = input
+fn parse_points(input: &[&str]) -> Vec<Point3> {
+    input
         .iter()
         .map(|line| {
-            let mut nums = line.split(',');
-            let x = nums
-                .next()
-                .unwrap_or_else(|| panic!("no x in line: {}", line))
-                .parse::<usize>()
-                .unwrap_or_else(|e| panic!("could not parse x from {}: {}", line, e));
-            let y = nums
-                .next()
-                .unwrap_or_else(|| panic!("no y in line: {}", line))
-                .parse::<usize>()
-                .unwrap_or_else(|e| panic!("could not parse y from {}: {}", line, e));
-            let z = nums
-                .next()
-                .unwrap_or_else(|| panic!("no z in line: {}", line))
-                .parse::<usize>()
-                .unwrap_or_else(|e| panic!("could not parse z from {}: {}", line, e));
-
-            Point3::new(x, y, z)
+            line.parse::<Point3>()
+                .unwrap_or_else(|e| panic!("could not parse `{line}`: {e}"))
         })
-        .collect::<Vec<_>>();
-
-    // calculate distance between all point pairs and sort
-    let distances = {
-        let mut distances = vec![];
-        for i in 0..points.len() {
-            for j in i + 1..points.len() {
-                let distance = points[i].euclidean_distance(points[j]);
-                distances.push((distance, &points[i], &points[j]));
-            }
-        }
-
-        distances.sort_by(|a, b| a.0.total_cmp(&b.0));
-
-        distances
-    };
-
-    // dbg!(&distances);
-
-    // create circuits out of the first n point pairs
-    let mut point_to_circuit: HashMap<&Point3, usize> = HashMap::new();
-    let mut circuits: HashMap<usize, HashSet<&Point3>> = HashMap::new();
-    let mut next_circuit_id = 0;
-    let mut result = 0;
-
-    for i in 0..distances.len() {
-        let (_d, a, b) = distances[i];
-
-        if let Some(a_cid) = point_to_circuit.get(a)
-            && let Some(b_cid) = point_to_circuit.get(b)
-        {
-            if *a_cid != *b_cid {
-                let to_merge = b_cid.clone();
-                let merged_cid = a_cid.clone();
-
-                for (_, cid) in point_to_circuit.iter_mut() {
-                    if *cid == to_merge {
-                        *cid = merged_cid;
-                    }
-                }
-
-                if let Some(points_to_merge) = circuits.remove(&to_merge) {
-                    circuits
-                        .entry(merged_cid)
-                        .and_modify(|points| (*points).extend(points_to_merge.into_iter()));
-                }
-            }
-        } else if let Some(cid) = point_to_circuit.get(a) {
-            circuits.entry(*cid).and_modify(|points| {
-                (*points).insert(b);
-            });
-            point_to_circuit.insert(b, *cid);
-        } else if let Some(cid) = point_to_circuit.get(b) {
-            circuits.entry(*cid).and_modify(|points| {
-                (*points).insert(a);
-            });
-            point_to_circuit.insert(a, *cid);
-        } else {
-            circuits.insert(next_circuit_id, HashSet::from([a, b]));
-            point_to_circuit.insert(a, next_circuit_id);
-            point_to_circuit.insert(b, next_circuit_id);
-            next_circuit_id += 1;
-        }
+        .collect()
+}
 
-        // we found the last connection if there is one circuit that contains all the points
-        if circuits.len() == 1 && circuits.iter().last().unwrap().1.len() == points.len() {
-            result = a.x * b.x;
-            break;
+// Every pair of point indices, closest first.
+fn closest_pairs(points: &[Point3]) -> Vec<(usize, usize)> {
+    let mut pairs = vec![];
+    for i in 0..points.len() {
+        for j in i + 1..points.len() {
+            pairs.push((points[i].squared_distance(points[j]), i, j));
         }
     }
 
-    // dbg!(&point_to_circuit);
-    // dbg!(&circuits);
+    pairs.sort_by_key(|(distance, _, _)| *distance);
 
-    result
+    pairs.into_iter().map(|(_, i, j)| (i, j)).collect()
 }
 
 #[cfg(test)]